@@ -1,4 +1,34 @@
-use clap::Parser;
+use clap::{ArgEnum, Parser};
+
+/// Rule used to update opinions from heard gossip
+#[derive(ArgEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OpinionModelKind {
+    /// Trust weighted accumulation into a decaying seed
+    #[default]
+    Accumulate,
+    /// DeGroot weighted averaging
+    Degroot,
+    /// Deffuant bounded confidence
+    BoundedConfidence,
+    /// Trust weighted Gaussian belief update
+    Bayesian,
+}
+
+/// Model the initial relationship graph is generated from
+#[derive(ArgEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NetworkKind {
+    /// A couple of random rivals per agent
+    #[default]
+    Rivals,
+    /// Independent random ties
+    ErdosRenyi,
+    /// Small world, a rewired ring lattice
+    WattsStrogatz,
+    /// Scale free, grown by preferential attachment
+    BarabasiAlbert,
+    /// Factions with friends inside and rivals across
+    StochasticBlock,
+}
 
 /// Simulation core
 #[derive(Parser, Debug, Default)]
@@ -7,6 +37,78 @@ pub struct Args {
     ///Initial population size
     #[clap(short, long, default_value_t = 80)]
     pub population: usize,
+
+    ///Steering weight for moving towards the current target
+    #[clap(long, default_value_t = 1.0)]
+    pub seek_weight: f32,
+
+    ///Steering weight for keeping distance from any nearby agent
+    #[clap(long, default_value_t = 1.5)]
+    pub separation_weight: f32,
+
+    ///Steering weight for moving away from nearby disliked agents
+    #[clap(long, default_value_t = 1.2)]
+    pub avoidance_weight: f32,
+
+    ///Steering weight for moving towards nearby liked agents
+    #[clap(long, default_value_t = 0.4)]
+    pub cohesion_weight: f32,
+
+    ///Behavior tree definition (RON) driving every agent instead of the built in logic
+    #[clap(long)]
+    pub behavior_file: Option<String>,
+
+    ///Big Five trait distributions (RON) new agents draw their personality from
+    #[clap(long)]
+    pub personality_file: Option<String>,
+
+    ///Abstract topics (RON) agents hold stances on and spread besides gossip about people
+    #[clap(long)]
+    pub topics_file: Option<String>,
+
+    ///Factions (RON) agents may belong to
+    #[clap(long)]
+    pub faction_file: Option<String>,
+
+    ///Rule used to update opinions from heard gossip
+    #[clap(long, arg_enum, default_value = "accumulate")]
+    pub opinion_model: OpinionModelKind,
+
+    ///Number of other people an agent can remember, least salient ones are forgotten beyond it
+    #[clap(long, default_value_t = 150)]
+    pub memory_capacity: usize,
+
+    ///How many times more likely agents gossip about the most relevant person than the least relevant one
+    #[clap(long, default_value_t = 4.0)]
+    pub topic_bias: f64,
+
+    ///Model the initial relationship graph is generated from
+    #[clap(long, arg_enum, default_value = "rivals")]
+    pub network: NetworkKind,
+
+    ///Relationship graph (RON) to start from instead of a generated one
+    #[clap(long)]
+    pub network_file: Option<String>,
+
+    ///Average number of relationships per agent in a generated network
+    #[clap(long, default_value_t = 4)]
+    pub network_degree: usize,
+
+    ///Share of generated relationships that are friendships, the rest are rivalries
+    #[clap(long, default_value_t = 0.5)]
+    pub friend_ratio: f64,
+
+    ///Chance to rewire each tie of the small world network
+    #[clap(long, default_value_t = 0.1)]
+    pub rewire_probability: f64,
+
+    ///Number of blocks in the stochastic block model, each becomes a faction of its own
+    #[clap(long, default_value_t = 2)]
+    pub sbm_blocks: usize,
+
+    ///How many times likelier a relationship is inside a block than across blocks of the stochastic block model
+    #[clap(long, default_value_t = 8.0)]
+    pub sbm_affinity: f64,
 }
 
 pub fn parse() -> Args {
@@ -1,15 +1,115 @@
-use super::cli::Args;
+use super::cli::{Args, NetworkKind};
 use bevy::core::FixedTimestep;
 use bevy::prelude::*;
 use bevy_prototype_debug_lines::*;
 use rand::prelude::*;
-use std::collections::HashMap;
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
 use std::f64::consts::{E, PI};
 use uuid::Uuid;
 
+mod behavior;
+mod network;
+mod opinion_model;
+mod people;
+
+use behavior::{BehaviorTree, Blackboard, Intent};
+use network::{Network, NetworkConfig, Tie};
+use opinion_model::{Claim, OpinionModel};
+use people::People;
+
 // ============ CONSTANTS ============
 
+// opinions
 pub const LOGISTIC_OPINION_SCALE: f64 = -0.01;
+pub const OPINION_SEED_DECAY: f64 = 0.05;
+pub const HEARSAY_WEIGHT: f64 = 0.2;
+pub const TRUST_LEARNING_RATE: f64 = 10.0;
+pub const IN_GROUP_BIAS: f64 = 25.0;
+pub const CONTRADICTION_MARGIN: f64 = 100.0;
+
+// retelling, distortion and lies
+pub const MAX_DISTORTION_CHANCE: f64 = 0.2;
+pub const EXAGGERATION: f64 = 1.5;
+pub const MAX_LIE_CHANCE: f64 = 0.5;
+pub const LIE_PENALTY: f64 = 2.0 * TRUST_LEARNING_RATE;
+pub const DRIFT_REPORT_INTERVAL: f64 = 10.0;
+
+// memory
+pub const RANKING_VIEW_SIZE: usize = 3;
+pub const OPINION_HALF_LIFE: f64 = 120.0;
+pub const MEMORY_TICK: f64 = 1.0;
+pub const PROVENANCE_LENGTH: usize = 8;
+
+// kinematics
+pub const BASE_MAX_SPEED: f32 = 100.0;
+pub const MAX_STEERING_FORCE: f32 = 500.0;
+pub const STEERING_RESPONSE_TIME: f32 = 0.25;
+pub const MAX_FRAME_DELTA: f32 = 0.1;
+
+// steering
+pub const ARRIVAL_RADIUS: f32 = 80.0;
+pub const WANDER_DISTANCE: f32 = 200.0;
+pub const SEPARATION_RADIUS: f32 = 40.0;
+pub const PERCEPTION_RADIUS: f32 = 150.0;
+pub const AVOIDANCE_LIKEABILITY: f64 = -20.0;
+pub const COHESION_LIKEABILITY: f64 = 20.0;
+
+// goals and locations
+pub const LOCATION_MEMORY_RATE: f64 = 0.1;
+pub const DELIBERATION_CHANCE: usize = 10;
+
+// speech and conversation
+pub const SPEECH_RADIUS: f32 = 150.0;
+pub const WHISPER_RADIUS: f32 = 40.0;
+pub const ANNOUNCE_RADIUS: f32 = 300.0;
+pub const LISTENING_HEARING_BOOST: f32 = 1.5;
+pub const AUDIENCE_RELEVANCE: f64 = 2.0;
+pub const INTERLOCUTOR_RADIUS: f32 = 100.0;
+pub const CONVERSATION_RADIUS: f32 = 60.0;
+pub const CONVERSATION_CHANCE: f64 = 0.05;
+
+// gatherings
+pub const GATHERING_TICK: f64 = 1.0;
+pub const GATHERING_RADIUS: f32 = 80.0;
+pub const GATHERING_SEAT_RADIUS: f32 = 35.0;
+pub const MIN_GATHERING_SIZE: usize = 3;
+pub const MAX_GATHERING_SIZE: usize = 6;
+pub const GATHERING_COOLDOWN: f64 = 15.0;
+pub const BOREDOM_RATE: f32 = 0.05;
+
+// firsthand encounters
+pub const ENCOUNTER_TICK: f64 = 0.5;
+pub const ENCOUNTER_RADIUS: f32 = 35.0;
+pub const ENCOUNTER_CHANCE: f64 = 0.1;
+
+// factions
+pub const FACTION_BIAS: f64 = 30.0;
+pub const FACTION_GOSSIP_WEIGHT: f64 = 0.5;
+pub const FACTION_PALETTE: [(f32, f32, f32); 5] = [
+    (0.9, 0.4, 0.4),
+    (0.4, 0.6, 0.95),
+    (0.5, 0.85, 0.5),
+    (0.95, 0.8, 0.4),
+    (0.75, 0.5, 0.9),
+];
+
+// emotion and emotes
+pub const VALENCE_HALF_LIFE: f64 = 20.0;
+pub const AROUSAL_HALF_LIFE: f64 = 6.0;
+pub const MOOD_THRESHOLD: f64 = 0.25;
+pub const FRIEND_EMPATHY: f64 = 0.5;
+pub const EMOTE_COLUMNS: usize = 10;
+pub const STRONG_JUDGEMENT: f64 = 60.0;
+
+// retorts to gossip about oneself
+pub const RETORT_CHANCE: f64 = 0.5;
+pub const GRUDGE_PATIENCE: f64 = 15.0;
+
+// reputation
+pub const PRESTIGE_WEIGHT: f64 = 0.5;
+pub const REPUTATION_TICK: f64 = 2.0;
+pub const HUD_RANKS: usize = 5;
 
 // ============ RESOURCES ============
 
@@ -29,6 +129,282 @@ impl TransformState {
     }
 }
 
+#[derive(Clone)]
+pub struct LocationZone {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+impl LocationZone {
+    fn contains(&self, point: Vec3) -> bool {
+        self.center.truncate().distance(point.truncate()) <= self.radius
+    }
+
+    fn random_point(&self) -> Vec3 {
+        let mut rng = rand::thread_rng();
+        let angle: f32 = rng.gen_range(0.0..std::f32::consts::TAU);
+        let distance: f32 = rng.gen_range(0.0..self.radius * 0.8);
+
+        self.center + Vec3::new(angle.cos(), angle.sin(), 0.0) * distance
+    }
+}
+
+#[derive(Default)]
+pub struct LocationDirectory {
+    pub locations: HashMap<String, LocationZone>,
+}
+
+impl LocationDirectory {
+    fn get(&self, k: &String) -> Option<&LocationZone> {
+        self.locations.get(k)
+    }
+
+    fn location_at(&self, point: Vec3) -> Option<&String> {
+        self.locations
+            .iter()
+            .find(|(_, zone)| zone.contains(point))
+            .map(|(name, _)| name)
+    }
+}
+
+/// Normal distribution of a personality trait, samples are clamped into 0..1.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct TraitDistribution {
+    pub mean: f32,
+    pub std_dev: f32,
+}
+
+impl Default for TraitDistribution {
+    fn default() -> Self {
+        TraitDistribution {
+            mean: 0.5,
+            std_dev: 0.15,
+        }
+    }
+}
+
+impl TraitDistribution {
+    /// Box-Muller transform over two uniform draws.
+    fn sample(&self, rng: &mut impl Rng) -> f32 {
+        let u1: f32 = rng.gen_range(f32::EPSILON..1.0);
+        let u2: f32 = rng.gen_range(0.0..1.0);
+        let z = (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos();
+
+        (self.mean + z * self.std_dev).clamp(0.0, 1.0)
+    }
+}
+
+/// Population wide distributions new agents draw their personality from, optionally loaded from a RON file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PersonalityDistribution {
+    pub openness: TraitDistribution,
+    pub conscientiousness: TraitDistribution,
+    pub extraversion: TraitDistribution,
+    pub agreeableness: TraitDistribution,
+    pub neuroticism: TraitDistribution,
+}
+
+impl PersonalityDistribution {
+    fn load(path: &str) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+
+        ron::from_str(&source).map_err(|e| format!("{}: {}", path, e))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum TopicKind {
+    Idea,
+    Brand,
+    Belief,
+}
+
+impl TopicKind {
+    fn emote(&self) -> Icon {
+        match self {
+            TopicKind::Idea => Icon::Idea,
+            TopicKind::Brand => Icon::Brand,
+            TopicKind::Belief => Icon::Belief,
+        }
+    }
+}
+
+fn untinted() -> (f32, f32, f32) {
+    (1.0, 1.0, 1.0)
+}
+
+/// An idea, product or position agents can take a stance on.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TopicDefinition {
+    pub id: String,
+    pub kind: TopicKind,
+    /// Share of the population starting out with a stance, in 0..1.
+    pub adoption: f64,
+    /// Stance of the initial adopters, in -100..100.
+    pub stance: f64,
+    /// Emote atlas cell replacing the one of the topic's kind.
+    #[serde(default)]
+    pub icon: Option<Icon>,
+    /// Tint of the topic's icon.
+    #[serde(default = "untinted")]
+    pub color: (f32, f32, f32),
+}
+
+impl TopicDefinition {
+    fn emote(&self) -> Icon {
+        self.icon.unwrap_or_else(|| self.kind.emote())
+    }
+
+    fn tint(&self) -> Color {
+        let (r, g, b) = self.color;
+        Color::rgb(r, g, b)
+    }
+}
+
+/// Abstract topics in circulation besides people, optionally loaded from a RON file.
+#[derive(Default, Deserialize)]
+pub struct TopicDirectory {
+    pub topics: Vec<TopicDefinition>,
+}
+
+impl TopicDirectory {
+    fn load(path: &str) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+
+        ron::from_str(&source).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    fn get(&self, topic_id: &String) -> Option<&TopicDefinition> {
+        self.topics.iter().find(|topic| topic.id == *topic_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum FactionKind {
+    Team,
+    Family,
+    Club,
+}
+
+impl FactionKind {
+    /// How strongly membership binds, family ties most of all.
+    fn cohesion(&self) -> f64 {
+        match self {
+            FactionKind::Team => 1.0,
+            FactionKind::Family => 1.5,
+            FactionKind::Club => 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FactionDefinition {
+    pub id: String,
+    pub kind: FactionKind,
+    /// Share of the population belonging to the faction, in 0..1.
+    pub share: f64,
+    /// Tint of the members' sprites, an agent in several factions wears the first one's.
+    pub color: (f32, f32, f32),
+}
+
+/// Factions agents can belong to, optionally loaded from a RON file, along with who belongs to which.
+#[derive(Default, Deserialize)]
+pub struct FactionDirectory {
+    pub factions: Vec<FactionDefinition>,
+    #[serde(skip)]
+    pub members: HashMap<String, Vec<String>>,
+}
+
+impl FactionDirectory {
+    fn load(path: &str) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+
+        ron::from_str(&source).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    fn get(&self, faction_id: &str) -> Option<&FactionDefinition> {
+        self.factions
+            .iter()
+            .find(|faction| faction.id == *faction_id)
+    }
+
+    fn factions_of(&self, person_id: &String) -> &[String] {
+        self.members.get(person_id).map_or(&[], |factions| factions)
+    }
+
+    fn join(&mut self, person_id: &str, faction_id: &str) {
+        let factions = self.members.entry(person_id.to_string()).or_default();
+        if !factions.iter().any(|joined| joined == faction_id) {
+            factions.push(faction_id.to_string());
+        }
+    }
+}
+
+/// Per faction summary of intergroup feelings, refreshed periodically.
+#[derive(Default)]
+pub struct FactionMetrics {
+    pub statistics: Vec<FactionStatistics>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FactionStatistics {
+    pub faction_id: String,
+    pub members: usize,
+    /// Mean likeability members hold of fellow members, None when they do not know each other.
+    pub in_group: Option<f64>,
+    /// Mean likeability members hold of known members of other factions.
+    pub out_group: Option<f64>,
+}
+
+/// Standing of every agent in the eyes of the whole population, refreshed periodically.
+#[derive(Default)]
+pub struct Reputation {
+    /// Agents by reputation, best first, in -100..100.
+    /// Likeability held of an agent is summed over everyone and spread over the whole population,
+    /// so being liked by many beats being adored by a few.
+    pub ranking: Vec<(String, f64)>,
+}
+
+/// How far beliefs about people have drifted from how likeable they really are, refreshed periodically.
+#[derive(Default)]
+pub struct BeliefDrift {
+    /// Mean absolute difference between held likeability and merit, None until anyone knows anyone.
+    pub error: Option<f64>,
+}
+
+/// Rule heard gossip is folded into opinions with, picked on the command line.
+pub struct OpinionDynamics {
+    pub model: Box<dyn OpinionModel>,
+}
+
+/// Simulated time, advanced once per frame from the real clock.
+/// Frame hitches are capped at MAX_FRAME_DELTA so agents never teleport across the map.
+#[derive(Default)]
+pub struct SimulationClock {
+    pub delta: f32,
+    pub elapsed: f64,
+}
+
+/// Relative strength of each steering behavior when they are summed into a single force.
+pub struct SteeringWeights {
+    pub seek: f32,
+    pub separation: f32,
+    pub avoidance: f32,
+    pub cohesion: f32,
+}
+
+impl From<&Args> for SteeringWeights {
+    fn from(args: &Args) -> Self {
+        SteeringWeights {
+            seek: args.seek_weight,
+            separation: args.separation_weight,
+            avoidance: args.avoidance_weight,
+            cohesion: args.cohesion_weight,
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct SpriteRegistry {
     characters: HashMap<String, Handle<Image>>,
@@ -36,6 +412,8 @@ pub struct SpriteRegistry {
     thumbs_down: Handle<Image>,
     thought: Handle<Image>,
     speech: Handle<Image>,
+    /// Grid of 48x48 icons from icons.png, indexed by Icon.
+    emotes: Handle<TextureAtlas>,
 }
 
 impl SpriteRegistry {
@@ -52,6 +430,46 @@ impl SpriteRegistry {
     }
 }
 
+/// Cells of the emote atlas, in the order they are laid out in icons.png.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Icon {
+    Ear,
+    Happy,
+    Angry,
+    Anxious,
+    Praise,
+    Insult,
+    Question,
+    Idea,
+    Brand,
+    Belief,
+}
+
+impl Icon {
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Heart or lightning for a glowing or scathing judgement, None for anything milder.
+    fn judgement(likeability: f64) -> Option<Icon> {
+        if likeability >= STRONG_JUDGEMENT {
+            Some(Icon::Praise)
+        } else if likeability <= -STRONG_JUDGEMENT {
+            Some(Icon::Insult)
+        } else {
+            None
+        }
+    }
+}
+
+/// What a speech bubble shows: what is being talked about, tinted for topics, and the speaker's mood in a corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeechBubble {
+    pub content: Icon,
+    pub tint: Color,
+    pub mood: Option<Icon>,
+}
+
 // ============ COMPONENTS ============
 
 #[derive(Component)]
@@ -60,6 +478,10 @@ pub struct Agent;
 #[derive(Component)]
 pub struct ID(String);
 
+/// Place of the agent in the spawn order, the nodes of the social network refer to agents by it.
+#[derive(Component)]
+pub struct SpawnIndex(usize);
+
 impl ID {
     fn rand() -> Self {
         ID(Uuid::new_v4().to_string())
@@ -90,706 +512,4875 @@ impl Direction {
 
 #[derive(Component)]
 pub struct Body {
+    /// Units per second
     pub velocity: Vec3,
+    /// Units per second squared, recomputed from steering forces every frame.
+    pub acceleration: Vec3,
+    pub max_speed: f32,
+    pub mass: f32,
+    /// Point the agent is currently heading to, velocity is steered towards it rather than snapped.
+    pub target: Option<Vec3>,
 }
 
-#[derive(Component)]
-pub struct Brain;
+impl Body {
+    fn new(max_speed: f32, mass: f32) -> Self {
+        Body {
+            velocity: Vec3::ZERO,
+            acceleration: Vec3::ZERO,
+            max_speed,
+            mass,
+            target: None,
+        }
+    }
 
-#[derive(Component)]
-pub struct Personality {
-    pub chattiness: usize,
-}
+    fn is_moving(&self) -> bool {
+        self.velocity.length_squared() > 1.0
+    }
 
-impl Personality {
-    fn random() -> Self {
-        let mut rng = rand::thread_rng();
-        let chattiness: usize = rng.gen_range(0..100);
+    /// Change of velocity turning the current one into a full speed run along the desired direction.
+    fn steer_towards(&self, direction: Vec3) -> Vec3 {
+        if direction == Vec3::ZERO {
+            return Vec3::ZERO;
+        }
 
-        Personality { chattiness }
+        direction.normalize() * self.max_speed - self.velocity
     }
-}
 
-#[derive(Component)]
-pub struct Lifetime(Timer);
+    fn seek(&self, position: Vec3, target: Vec3) -> Vec3 {
+        self.steer_towards(target - position)
+    }
 
-#[derive(Component)]
-pub struct Voice;
+    /// Seek which slows down linearly once inside ARRIVAL_RADIUS, so agents stop at their target instead of orbiting it.
+    fn arrive(&self, position: Vec3, target: Vec3) -> Vec3 {
+        let offset = target - position;
+        let distance = offset.length();
 
+        if distance < 1.0 {
+            return -self.velocity;
+        }
+
+        let speed = self.max_speed * (distance / ARRIVAL_RADIUS).min(1.0);
+        offset / distance * speed - self.velocity
+    }
+
+    fn apply_force(&mut self, force: Vec3) {
+        self.acceleration = force.clamp_length_max(MAX_STEERING_FORCE) / self.mass;
+    }
+
+    /// Semi-implicit Euler step over `delta` seconds, the velocity is updated and capped at `max_speed` before it moves the agent.
+    fn integrate(&mut self, translation: &mut Vec3, max_speed: f32, delta: f32) {
+        self.velocity = (self.velocity + self.acceleration * delta).clamp_length_max(max_speed);
+
+        translation.x += self.velocity.x * delta;
+        translation.y += self.velocity.y * delta;
+    }
+}
+
+/// What the agent is currently trying to do, chosen by the Brain's utility scoring.
+#[derive(Component, Debug, Clone, PartialEq, Default, Reflect)]
+#[reflect_value(PartialEq)]
+pub enum Goal {
+    #[default]
+    Wander,
+    SeekFriend(String),
+    AvoidRival(String),
+    GoToLocation(String),
+    JoinConversation(Vec3),
+    Rest,
+}
+
+/// Utility based decision making, every candidate goal gets a score in 0..1 and the best one wins.
+/// Scores of the last deliberation are kept around for debugging.
 #[derive(Component, Default)]
-pub struct Opinions {
-    people: HashMap<String, PersonalOpinion>,
-    favorite_person: (String, f64),
-    // locations could be used for determining whether areas are favorable to go to over long term, allowing for agents to learn where their friends tend to congregate
-    // locations : HashMap<String, LocationOpinion>,
+pub struct Brain {
+    pub considerations: Vec<(Goal, f32)>,
 }
 
-impl Opinions {
-    fn new(owner_id: String) -> Self {
-        let mut people = HashMap::<String, PersonalOpinion>::new();
-        //intial self-love can eventually be determined by provided Personality, as opposed to everyone starting feeling so-so about themselves
-        let opinion_of_self = PersonalOpinion::new(100.0, 100.0);
-        let fav_person_tuple = (owner_id.clone(), opinion_of_self.likeability.clone());
+impl Brain {
+    fn deliberate(
+        &mut self,
+        id: &ID,
+        position: Vec3,
+        personality: &Personality,
+        opinions: &Opinions,
+        transform_state: &TransformState,
+        location_directory: &LocationDirectory,
+    ) -> Goal {
+        let mut rng = rand::thread_rng();
+        let sociability = personality.extraversion;
 
-        people.insert(owner_id, opinion_of_self);
+        self.considerations.clear();
+        self.considerations.push((Goal::Wander, 0.2));
+        self.considerations.push((
+            Goal::Rest,
+            0.3 * (1.0 - sociability) * (1.0 - personality.conscientiousness / 2.0),
+        ));
 
-        Opinions {
-            people: people,
-            favorite_person: fav_person_tuple,
+        for (friend_id, likeability) in opinions.favorites() {
+            if transform_state.get(friend_id).is_some() {
+                let fondness = (likeability / 100.0) as f32;
+                let prestige = opinions.prestige(friend_id) as f32;
+                self.considerations.push((
+                    Goal::SeekFriend(friend_id.clone()),
+                    fondness.clamp(0.0, 1.0) * (0.5 + sociability / 2.0) * prestige,
+                ));
+            }
+        }
+
+        for (rival_id, likeability) in opinions.rivals() {
+            let distance = match transform_state.get(rival_id) {
+                Some(rival_transform) => position.distance(rival_transform.translation),
+                None => continue,
+            };
+
+            if distance < PERCEPTION_RADIUS {
+                let dislike = (-likeability / 100.0) as f32;
+                let proximity = 1.0 - distance / PERCEPTION_RADIUS;
+                self.considerations.push((
+                    Goal::AvoidRival(rival_id.clone()),
+                    dislike.clamp(0.0, 1.0) * proximity,
+                ));
+            }
         }
+
+        if let Some(location_id) = opinions.get_fav_location_id() {
+            let inside = location_directory
+                .get(&location_id)
+                .is_none_or(|zone| zone.contains(position));
+            if !inside {
+                let appeal = opinions.location_appeal(&location_id).unwrap_or(0.0) / 100.0;
+                self.considerations.push((
+                    Goal::GoToLocation(location_id),
+                    0.8 * (appeal as f32).clamp(0.0, 1.0),
+                ));
+            }
+        }
+
+        if let Some((center, warmth)) = nearby_conversation(id, position, opinions, transform_state)
+        {
+            self.considerations.push((
+                Goal::JoinConversation(center),
+                warmth.clamp(0.0, 1.0) * sociability,
+            ));
+        }
+
+        // a little noise keeps agents with similar circumstances from acting in lockstep
+        for (_, score) in self.considerations.iter_mut() {
+            *score *= rng.gen_range(0.9..1.1);
+        }
+
+        self.considerations
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(goal, _)| goal.clone())
+            .unwrap_or_default()
     }
+}
 
-    fn check_if_new_favorite(
-        &mut self,
-        candidate_opinion: &PersonalOpinion,
-        candidate_id: &String,
-    ) {
-        let likeability_threshold = self.favorite_person.1;
-        if candidate_opinion.likeability > likeability_threshold {
-            self.favorite_person = (candidate_id.clone(), candidate_opinion.likeability.clone());
+/// Big Five personality model, every trait lies in 0..1.
+#[derive(Component, Debug, Clone)]
+pub struct Personality {
+    /// How willing the agent is to take in what others tell it.
+    pub openness: f32,
+    pub conscientiousness: f32,
+    /// Drives how often the agent speaks up.
+    pub extraversion: f32,
+    /// Sets the tone of first impressions.
+    pub agreeableness: f32,
+    /// Scales how strongly heard gossip sways the agent.
+    pub neuroticism: f32,
+}
+
+impl Personality {
+    fn random(distribution: &PersonalityDistribution) -> Self {
+        let mut rng = rand::thread_rng();
+
+        Personality {
+            openness: distribution.openness.sample(&mut rng),
+            conscientiousness: distribution.conscientiousness.sample(&mut rng),
+            extraversion: distribution.extraversion.sample(&mut rng),
+            agreeableness: distribution.agreeableness.sample(&mut rng),
+            neuroticism: distribution.neuroticism.sample(&mut rng),
         }
     }
 
-    fn get_fav_person_id(&self) -> String {
-        self.favorite_person.0.clone()
+    /// Chance to speak up, out of 10000, on every say tick.
+    fn chattiness(&self) -> usize {
+        (self.extraversion * 100.0) as usize
     }
 
-    /// At Presemt, this implements a random choice from a set of held opinions to an output statement
-    /// future iterations should select statements based on their relevance
-    /// relevance can be held in a kind of conceptual hierarchy component along with status.
-    /// these could both be sorted vectors of tuples (score, topic), whose score defines a ranking on individuals.
-    /// A choice from the vector should be a weighted random draw, where 0 is k times more likely than n for a vec.len() = n + 1;
-    /// should define a pretty straightforward curve, can possibly do with rounding on a curve func with input set to int;
-    fn generate_speakable_personal_opinion(
-        &mut self,
-        transform: &Transform,
-        id: &ID,
-        identity: &Identity,
-    ) -> SpokenEvent {
-        let person = self.people.keys().choose(&mut rand::thread_rng());
+    /// Extraverts tend to bustle around, introverts take their time.
+    fn preferred_speed(&self) -> f32 {
+        BASE_MAX_SPEED * (0.75 + self.extraversion / 2.0)
+    }
 
-        let topic: String = person.map(|s| s.to_string()).unwrap_or(id.0.clone());
+    /// Bias of first impressions, in -40..40.
+    fn impression_bias(&self) -> f64 {
+        (self.agreeableness as f64 - 0.5) * 80.0
+    }
 
-        let opinion: PersonalOpinion = self
-            .people
-            .get(&topic)
-            .expect("Could not get opinion")
-            .clone();
+    /// Seeds of the opinion an agent holds of itself, as (trust, likeability).
+    /// Emotionally stable and conscientious agents are sure of themselves, anxious ones are not.
+    fn self_regard(&self) -> (f64, f64) {
+        let confidence = 40.0 + 120.0 * self.conscientiousness as f64;
+        let esteem = 20.0
+            + 120.0 * (1.0 - self.neuroticism as f64)
+            + 40.0 * (self.extraversion as f64 - 0.5);
 
-        let event = SpokenEvent {
-            author: id.0.clone(),
-            origin: transform.translation,
-            distance: 150.0,
-            identity: identity.0.clone(),
-            opinion: Some((topic.clone(), opinion.clone())),
-        };
+        (confidence, esteem)
+    }
 
-        event
-        //let opinion = opinions.people.get(person);
+    /// How much sharing looks with someone sways a first impression, open minded agents care less.
+    fn in_group_bias(&self) -> f64 {
+        IN_GROUP_BIAS * (1.0 - self.openness as f64 / 2.0)
     }
-}
 
-#[derive(Component, Clone)]
-pub struct PersonalOpinion {
-    trust_seed: f64,
-    pub trust: f64,
-    likeability_seed: f64,
-    pub likeability: f64,
-}
+    /// Multiplier on opinion shifts caused by heard gossip, in 0.5..1.5.
+    fn susceptibility(&self) -> f64 {
+        0.5 + self.neuroticism as f64
+    }
 
-impl PersonalOpinion {
-    /// Simple tethered adjust, can grow more specific, situational, and complex as sim develops
-    /// trust is not really integrated yet, as it increases complexity significantly,
-    /// and is dependant on not-yet-implemented contradiction detection.
-    fn adjust_trust(&mut self, modifier_value: f64) {
-        self.trust = self.trust_seed + modifier_value;
-        self.likeability = self.likeability_seed + (0.5 * modifier_value);
-        self.propegate_output_values();
+    /// Probability of taking a piece of gossip into account at all.
+    fn receptiveness(&self) -> f64 {
+        0.25 + 0.75 * self.openness as f64
     }
 
-    /// Simple tethered adjust, can grow more specific, situational, and complex as sim develops.
-    fn adjust_likeability(&mut self, modifier_value: f64) {
-        self.trust = self.trust_seed + (0.5 * modifier_value);
-        self.likeability = self.likeability_seed + modifier_value;
-        self.propegate_output_values();
+    /// Chance to strike up a conversation with someone nearby, on every say tick.
+    fn sociability(&self) -> f64 {
+        CONVERSATION_CHANCE * self.extraversion as f64
     }
 
-    /// Logistic function provides high stability around extreme affection, and extreme dislike, but more variability while on the fence.
-    /// The degree to which variabiliy happens around origin is determined by const LOGISTIC_OPINION_SCALE.
-    fn propegate_output_values(&mut self) {
-        self.trust = (200.0 / (1.0 + E.powf(LOGISTIC_OPINION_SCALE * self.trust_seed))) - 100.0;
-        self.likeability =
-            (200.00 / (1.0 + E.powf(LOGISTIC_OPINION_SCALE * self.likeability_seed))) - 100.0;
+    /// Boredom gained on every turn spent in a gathering, introverts tire of company sooner.
+    fn boredom_rate(&self) -> f32 {
+        BOREDOM_RATE * (1.5 - self.extraversion)
     }
 
-    fn new(init_trust: f64, init_likeability: f64) -> Self {
-        let mut output_opinion = PersonalOpinion {
-            trust: 0.0,
-            likeability: 0.0,
-            trust_seed: init_trust,
-            likeability_seed: init_likeability,
-        };
-        output_opinion.propegate_output_values();
+    /// Ground truth of how likeable the agent really is, in -100..100.
+    /// Beliefs others hold about it are measured against this.
+    fn merit(&self) -> f64 {
+        150.0 * (self.agreeableness as f64 - 0.5) + 50.0 * (self.conscientiousness as f64 - 0.5)
+    }
 
-        output_opinion
+    /// Chance to misrepresent an opinion when a lie would please the audience,
+    /// disagreeable and unscrupulous agents lie the most.
+    fn dishonesty(&self) -> f64 {
+        MAX_LIE_CHANCE * (1.0 - (self.agreeableness + self.conscientiousness) as f64 / 2.0)
     }
-}
 
-// ============ EVENTS ============
+    /// How pleasant a brush with `other` feels, in -100..100 before luck plays its part.
+    /// Agreeable company and a similar energy help, anxious agents take things badly.
+    fn rapport(&self, other: &Personality) -> f64 {
+        let kindness = other.agreeableness as f64 - 0.5;
+        let similarity = 0.5 - (self.extraversion - other.extraversion).abs() as f64;
+        let anxiety = self.neuroticism as f64 - 0.5;
 
-struct SpokenEvent {
-    author: String,
-    origin: Vec3,
-    distance: f32,
-    identity: Handle<Image>,
-    opinion: Option<(String, PersonalOpinion)>,
-}
-// ============ SYSTEM LABELS ============
+        (100.0 * kindness + 50.0 * similarity - 50.0 * anxiety).clamp(-100.0, 100.0)
+    }
 
-#[derive(SystemLabel, Clone, Hash, Debug, Eq, PartialEq)]
-enum StartupLabels {
-    LoadSprites,
-    PopulateSim,
-    MakeRivals,
-}
+    /// Chance to notice a claim at odds with what the agent saw for itself.
+    fn lie_detection(&self) -> f64 {
+        0.5 + 0.5 * self.openness as f64
+    }
 
-// ============ PLUGIN ============
-pub struct SimulationPlugin;
+    /// Careless agents now and then garble what they retell,
+    /// extraverts by exaggerating, anxious ones by getting it backwards and closed minded ones by mixing people up.
+    fn distortion(&self) -> Option<Distortion> {
+        let mut rng = thread_rng();
+        if !rng.gen_bool(MAX_DISTORTION_CHANCE * (1.0 - self.conscientiousness as f64)) {
+            return None;
+        }
 
-impl Plugin for SimulationPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_event::<SpokenEvent>()
-            .init_resource::<TransformState>()
-            .init_resource::<FaceDirectory>()
-            .init_resource::<SpriteRegistry>()
-            .add_startup_system(load_sprites_startup.label(StartupLabels::LoadSprites))
-            .add_startup_system(setup_startup)
-            .add_startup_system(
-                populate_sim_startup
-                    .label(StartupLabels::PopulateSim)
-                    .after(StartupLabels::LoadSprites),
-            )
-            .add_startup_system(
-                make_rivals_startup
-                    .label(StartupLabels::MakeRivals)
-                    .after(StartupLabels::LoadSprites),
-            )
-            .add_startup_system(report_agent_transform_system.after(StartupLabels::PopulateSim))
-            .add_system_set(
-                SystemSet::new()
-                    .with_run_criteria(FixedTimestep::step(0.1))
-                    .with_system(animate_sprite_system)
-                    .with_system(executive_functioning_system),
-            )
-            .add_system_set(
-                SystemSet::new()
-                    .with_run_criteria(FixedTimestep::step(0.2))
-                    .with_system(say_system),
-            )
-            .add_system_set(
-                SystemSet::new()
-                    .with_run_criteria(FixedTimestep::step(0.01))
-                    .with_system(boundaries_system)
-                    .with_system(physics_system)
-                    .with_system(report_agent_transform_system),
-            )
-            .add_system(direct_sprite_system)
-            .add_system(thought_system)
-            .add_system(lifetime_despawn_system);
+        [
+            (Distortion::Exaggeration, self.extraversion),
+            (Distortion::SignFlip, self.neuroticism),
+            (Distortion::Misattribution, 1.0 - self.openness),
+        ]
+        .choose_weighted(&mut rng, |(_, weight)| weight + 0.01)
+        .ok()
+        .map(|(distortion, _)| *distortion)
     }
-}
 
-// ============ STARTUP SYSTEMS ============
+    /// How the agent answers being badmouthed within earshot, if at all.
+    /// Bold, steady agents confront the gossip, touchy and spiteful ones spread dirt back.
+    fn retort(&self, likeability: f64) -> Option<Retort> {
+        let mut rng = thread_rng();
+        let severity = (-likeability / 100.0).clamp(0.0, 1.0);
+        let chance = RETORT_CHANCE * severity * (1.5 - self.agreeableness as f64);
+        if !rng.gen_bool(chance.clamp(0.0, 1.0)) {
+            return None;
+        }
 
-fn setup_startup(mut commands: Commands) {
-    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
-    commands.spawn_bundle(UiCameraBundle::default());
-}
+        [
+            (
+                Retort::Confront,
+                self.extraversion * (1.0 - self.neuroticism),
+            ),
+            (
+                Retort::Retaliate,
+                self.neuroticism * (1.0 - self.agreeableness),
+            ),
+        ]
+        .choose_weighted(&mut rng, |(_, weight)| weight + 0.01)
+        .ok()
+        .map(|(retort, _)| *retort)
+    }
 
-fn populate_sim_startup(
-    mut commands: Commands,
-    sprites: Res<SpriteRegistry>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
-    mut face_directory: ResMut<FaceDirectory>,
-    args: Res<Args>,
-) {
-    info!("Populating simulation");
-    for _ in 0..args.population {
-        make_rand_character(
-            &mut commands,
-            &sprites,
-            &mut texture_atlases,
-            &mut face_directory,
-        )
+    /// Only the most extraverted agents shout their gossip across the square.
+    fn speaking_register(&self) -> Register {
+        if thread_rng().gen_bool((self.extraversion as f64).powi(3)) {
+            Register::Announce
+        } else {
+            Register::Say
+        }
     }
 }
 
-fn load_sprites_startup(
-    asset_server: Res<AssetServer>,
-    mut sprite_registry: ResMut<SpriteRegistry>,
-) {
-    info!("Loading population sprites");
-    let sprites = vec![
-        "lady.png",
-        "baldguy.png",
-        "coollady.png",
-        "princessleia.png",
-        "blondedude.png",
-        "hatguy.png",
-        "redhead.png",
-        "jacketguy.png",
-    ];
-
-    let sprites_map: HashMap<String, Handle<Image>> = sprites
-        .iter()
-        .map(|sprite| {
-            let sprite_handle: Handle<Image> = asset_server.load(sprite.to_owned());
-            (sprite.to_string(), sprite_handle)
-        })
-        .collect();
+/// Two agents standing still and taking turns to talk to each other.
+#[derive(Component, Debug)]
+pub struct Conversation {
+    pub partner: Entity,
+    pub partner_id: String,
+    pub speaking: bool,
+    /// Turns left for both sides together, the conversation ends when none remain.
+    pub turns_left: u32,
+}
 
-    sprite_registry.characters = sprites_map;
+/// Factions the agent belongs to, mirrored in the FactionDirectory so others can look them up.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Factions(pub Vec<String>);
 
-    info!("Loading bubble sprites");
-    sprite_registry.thought = asset_server.load("thoughtbubble.png");
-    sprite_registry.speech = asset_server.load("textbubble.png");
+/// A conversation circle, lives on its own entity and is attended by its members.
+#[derive(Component, Debug)]
+pub struct Gathering {
+    pub center: Vec3,
+    pub members: Vec<(Entity, String)>,
+    /// Index of the member whose turn it is to speak.
+    pub speaker: usize,
+}
 
-    info!("Loading sentiment sprites");
-    sprite_registry.thumbs_up = asset_server.load("good_thumbs_up.png");
-    sprite_registry.thumbs_down = asset_server.load("bad_thumbs_down.png");
+#[derive(Component, Debug)]
+pub struct Attending {
+    pub gathering: Entity,
+    /// Members leave once it reaches 1.
+    pub boredom: f32,
 }
 
-fn make_rivals_startup(mut query: Query<&mut Opinions>, face_directory: Res<FaceDirectory>) {
-    for mut opinions in query.iter_mut() {
-        let person = face_directory
-            .faces
-            .keys()
-            .choose(&mut rand::thread_rng())
-            .unwrap();
-        let person2 = face_directory
-            .faces
-            .keys()
-            .choose(&mut rand::thread_rng())
-            .unwrap();
-        opinions
-            .people
-            .insert(person.clone(), PersonalOpinion::new(-100.0, -100.0));
-        opinions
-            .people
-            .insert(person2.clone(), PersonalOpinion::new(-100.0, -100.0));
-    }
+/// When the agent last left a gathering, it keeps to itself for GATHERING_COOLDOWN seconds afterwards.
+#[derive(Component, Debug)]
+pub struct LeftGathering {
+    pub at: f64,
 }
 
-// ============ SYSTEMS ============
+/// Walking up to someone overheard badmouthing the agent, to set the record straight.
+#[derive(Component, Debug)]
+pub struct Confronting {
+    pub speaker_id: String,
+    pub since: f64,
+}
 
-fn animate_sprite_system(mut query: Query<(&mut TextureAtlasSprite, &Body, &Direction)>) {
-    for (mut sprite, body, direction) in query.iter_mut() {
-        if body.velocity.x.abs() < 0.01 && body.velocity.y.abs() < 0.01 {
-            continue;
-        }
-        let row = direction.spritesheet_row();
-        sprite.index = (row * 3 as usize) + (sprite.index + 1) % 3;
-    }
+/// Waiting for an audience to badmouth someone who badmouthed the agent first.
+#[derive(Component, Debug)]
+pub struct Retaliating {
+    pub speaker_id: String,
+    pub since: f64,
 }
 
-fn make_rand_character(
-    commands: &mut Commands,
-    sprites: &Res<SpriteRegistry>,
-    texture_atlases: &mut ResMut<Assets<TextureAtlas>>,
-    face_directory: &mut ResMut<FaceDirectory>,
-) {
-    let sprite = sprites.random_character();
-    let sprite_handle: Handle<Image> = sprites.get_character(&sprite);
+#[derive(Component)]
+pub struct Lifetime(Timer);
 
-    let texture_atlas = TextureAtlas::from_grid(sprite_handle.clone(), Vec2::new(52.0, 72.0), 3, 4);
-    let texture_atlas_handle = texture_atlases.add(texture_atlas);
+#[derive(Component)]
+pub struct Voice;
 
-    let id = ID::rand();
-    let num_name = id.0.clone();
+/// Icon floating above an agent's head showing its mood, hidden while calm.
+#[derive(Component)]
+pub struct Emote;
 
-    face_directory
-        .faces
-        .insert(num_name.clone(), sprite_handle.clone());
+/// Text listing the most reputable agents.
+#[derive(Component)]
+pub struct ReputationHud;
 
-    let mut rng = rand::thread_rng();
-    let initial_location: (f32, f32) = (rng.gen_range(-200.0..200.0), rng.gen_range(-200.0..200.0));
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mood {
+    Calm,
+    Happy,
+    Angry,
+    Anxious,
+}
 
-    commands
-        .spawn_bundle(SpriteSheetBundle {
-            texture_atlas: texture_atlas_handle,
-            transform: Transform {
-                translation: Vec3::new(initial_location.0, initial_location.1, 1.0),
-                scale: Vec3::new(1.0, 1.0, 1.0),
-                ..Default::default()
-            },
-            ..Default::default()
-        })
-        .insert(id)
-        .insert(Identity(sprite_handle.clone()))
-        .insert(Body {
-            velocity: 0.0 * Vec3::new(0.5, -0.5, 0.0).normalize(),
-        })
-        .insert(Direction::Right)
-        .insert(Voice)
-        .insert(Personality::random())
-        .insert(Opinions::new(num_name.clone()))
-        .insert(Brain);
+impl Mood {
+    /// Emote showing the mood, calm agents show nothing.
+    fn emote(&self) -> Option<Icon> {
+        match self {
+            Mood::Calm => None,
+            Mood::Happy => Some(Icon::Happy),
+            Mood::Angry => Some(Icon::Angry),
+            Mood::Anxious => Some(Icon::Anxious),
+        }
+    }
 }
 
-fn executive_functioning_system(
-    mut query: Query<(&mut Body, &Opinions, &Transform), (With<Brain>, With<Direction>)>,
-    transform_state: Res<TransformState>,
-) {
-    let mut rng = rand::thread_rng();
+/// How the agent feels, stirred by what it hears about itself and its friends and settling back to calm over time.
+/// Arousal fades faster than valence, so a flare of anger cools into lingering unease.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Emotion {
+    /// Pleasantness of the feeling, in -1..1.
+    pub valence: f64,
+    /// Intensity of the feeling, in 0..1.
+    pub arousal: f64,
+}
 
-    for (mut body, opinions, transform) in query.iter_mut() {
-        let should_turn: usize = rng.gen_range(0..100);
-        let should_random: usize = rng.gen_range(0..100);
+impl Emotion {
+    /// Takes in news as good or as bad as `impact`, in -1..1, anxious agents take it harder.
+    fn feel(&mut self, impact: f64, personality: &Personality) {
+        let reactivity = 0.5 + personality.neuroticism as f64;
 
-        if should_turn <= 10 {
-            let actor_translation: Vec3 = transform.translation;
-            let favorite_person_id: String = opinions.get_fav_person_id();
+        self.valence = (self.valence + impact * reactivity).clamp(-1.0, 1.0);
+        self.arousal = (self.arousal + impact.abs() * reactivity).clamp(0.0, 1.0);
+    }
 
-            if let Some(target_transform) = transform_state.get(&favorite_person_id) {
-                let target_translation = target_transform.translation;
-                let non_normal_vec = target_translation - actor_translation.clone();
+    fn settle(&mut self, elapsed: f64) {
+        self.valence *= 0.5f64.powf(elapsed / VALENCE_HALF_LIFE);
+        self.arousal *= 0.5f64.powf(elapsed / AROUSAL_HALF_LIFE);
+    }
 
-                if non_normal_vec == Vec3::ZERO || should_random == 69 {
-                    let k: f64 = rng.gen_range(0.0..2000.0);
-                    let rads = k / 1000.0 * PI;
-                    body.velocity.x = rads.cos() as f32;
-                    body.velocity.y = rads.sin() as f32;
-                } else {
-                    body.velocity = non_normal_vec.normalize();
-                }
-            }
+    fn mood(&self) -> Mood {
+        if self.valence > MOOD_THRESHOLD {
+            Mood::Happy
+        } else if self.valence < -MOOD_THRESHOLD && self.arousal > 0.5 {
+            Mood::Angry
+        } else if self.valence < -MOOD_THRESHOLD {
+            Mood::Anxious
+        } else {
+            Mood::Calm
         }
     }
-}
-
-fn direct_sprite_system(
-    mut query: Query<(&Body, &mut Direction), (Changed<Body>, With<Transform>)>,
-) {
-    for (body, mut direction) in query.iter_mut() {
-        let x = body.velocity.x;
-        let y = body.velocity.y;
 
-        *direction = if x.abs() > y.abs() {
-            if x < 0.0 {
-                Direction::Left
-            } else {
-                Direction::Right
-            }
-        } else {
-            if y < 0.0 {
-                Direction::Down
-            } else {
-                Direction::Up
-            }
-        };
+    /// Multiplier on the chance to speak up, worked up agents talk more and downcast ones less.
+    fn chattiness(&self) -> f64 {
+        (1.0 + self.arousal + 0.5 * self.valence).max(0.0)
+    }
 
-        debug!("vel -> dir => {:?} -> {:?}", body.velocity, direction);
+    /// Multiplier on the top speed, in 0.75..1.5, worked up agents hurry and downcast ones drag their feet.
+    fn speed(&self) -> f32 {
+        (1.0 + 0.5 * self.arousal + 0.25 * self.valence.min(0.0)) as f32
     }
 }
 
-fn physics_system(mut query: Query<(&Body, &mut Transform)>) {
-    for (body, mut transform) in query.iter_mut() {
-        transform.translation.x += body.velocity.x;
-        transform.translation.y += body.velocity.y;
-    }
+#[derive(Component, Default)]
+pub struct Opinions {
+    owner_id: String,
+    /// Along with everyone but the owner ranked from most to least liked, kept up to date on every change of opinion.
+    people: People,
+    // locations are used for determining whether areas are favorable to go to over long term, allowing for agents to learn where their friends tend to congregate
+    locations: HashMap<String, LocationOpinion>,
+    /// Stances on abstract topics, likeability standing for how much the agent is for or against it.
+    stances: HashMap<String, PersonalOpinion>,
 }
 
-fn report_agent_transform_system(
-    mut query: Query<(&Transform, &Body, &ID), With<Brain>>,
-    mut transform_global_state: ResMut<TransformState>,
-) {
-    for (transform, body, id) in query.iter_mut() {
-        transform_global_state
-            .transforms
-            .entry(id.0.clone())
-            .or_insert(transform.clone());
+impl Opinions {
+    fn new(owner_id: String, personality: &Personality) -> Self {
+        let mut people = People::new(owner_id.clone());
+        let (self_trust, self_esteem) = personality.self_regard();
+        let opinion_of_self = PersonalOpinion::new(self_trust, self_esteem);
+
+        people.insert(owner_id.clone(), opinion_of_self);
+
+        Opinions {
+            owner_id,
+            people: people,
+            locations: HashMap::new(),
+            stances: HashMap::new(),
+        }
     }
-}
 
-fn boundaries_system(mut query: Query<(&mut Body, &Transform), With<Direction>>) {
-    for (mut body, transform) in query.iter_mut() {
-        //these should be on window size, not manually hard-coded
-        let bounds = (
-            transform.translation.y > 400.0,
-            transform.translation.y < -400.0,
-            transform.translation.x > 700.0,
-            transform.translation.x < -700.0,
+    /// Takes up the stance of the topic's initial adopters.
+    fn adopt(&mut self, topic: &TopicDefinition) {
+        self.stances.insert(
+            topic.id.clone(),
+            PersonalOpinion::new(0.0, logistic_seed(topic.stance)),
         );
+    }
 
-        match bounds {
-            (true, _, _, _) => {
-                body.velocity.x = 0.0;
-                body.velocity.y = -1.0;
-            }
-            (_, true, _, _) => {
-                body.velocity.x = 0.0;
-                body.velocity.y = 1.0;
-            }
-            (_, _, true, _) => {
-                body.velocity.x = -1.0;
-                body.velocity.y = 0.0;
-            }
-            (_, _, _, true) => {
-                body.velocity.x = 1.0;
-                body.velocity.y = 0.0;
-            }
-            _ => {}
-        }
+    /// How much the agent currently wants to be at a location, pleasant memories plus the people it is fond of who tend to be there.
+    fn location_appeal(&self, location_id: &String) -> Option<f64> {
+        let location = self.locations.get(location_id)?;
+
+        let company: f64 = location
+            .met
+            .iter()
+            .filter_map(|(person_id, times)| {
+                self.people
+                    .get(person_id)
+                    .map(|opinion| opinion.likeability * *times as f64)
+            })
+            .sum();
+
+        Some(location.pleasantness + company / location.visits.max(1) as f64)
     }
-}
 
-fn say_system(
-    mut commands: Commands,
-    mut spoken_events: EventWriter<SpokenEvent>,
-    sprites: Res<SpriteRegistry>,
-    mut query: Query<(
-        Entity,
-        &ID,
-        &Identity,
-        &Transform,
-        &Personality,
-        &Voice,
-        &mut Opinions,
-    )>,
-) {
-    let mut rng = rand::thread_rng();
+    fn get_fav_location_id(&self) -> Option<String> {
+        self.locations
+            .keys()
+            .filter_map(|location_id| {
+                self.location_appeal(location_id)
+                    .map(|appeal| (location_id, appeal))
+            })
+            .filter(|(_, appeal)| *appeal > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(location_id, _)| location_id.clone())
+    }
 
-    for (entity, id, identity, transform, personality, _, mut opinions) in query.iter_mut() {
-        let should_think: usize = rng.gen_range(0..10000);
+    fn insert_opinion(&mut self, person_id: String, opinion: PersonalOpinion) {
+        self.people.insert(person_id, opinion);
+    }
 
-        if should_think <= personality.chattiness {
-            let texture = sprites.speech.clone();
+    /// Time based decay of every opinion but the one the agent holds of itself.
+    fn fade(&mut self, elapsed: f64) {
+        self.people.update_others(|opinion| opinion.fade(elapsed));
+    }
 
-            let child = commands
-                .spawn_bundle(SpriteBundle {
-                    texture: texture.clone(),
-                    transform: Transform {
-                        translation: Vec3::new(40.0, 45.0, 1.0),
-                        scale: Vec3::new(1.0, 1.0, 1.0),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(Lifetime(Timer::from_seconds(2.0, true)))
-                .id();
+    /// Dunbar style limit, the least salient people are forgotten until `capacity` others are remembered.
+    fn forget_beyond(&mut self, capacity: usize, now: f64) {
+        let known = self.people.len().saturating_sub(1);
+        if known <= capacity {
+            return;
+        }
 
-            commands.entity(entity).push_children(&[child]);
+        let mut by_salience: Vec<(String, f64)> = self
+            .people
+            .iter()
+            .filter(|(person_id, _)| **person_id != self.owner_id)
+            .map(|(person_id, opinion)| (person_id.clone(), opinion.salience(now)))
+            .collect();
+        by_salience.sort_by(|a, b| a.1.total_cmp(&b.1));
 
-            let event = opinions.generate_speakable_personal_opinion(&transform, &id, &identity);
-            spoken_events.send(event);
+        for (person_id, _) in by_salience.into_iter().take(known - capacity) {
+            self.people.remove(&person_id);
         }
     }
-}
 
-fn thought_system(
-    mut commands: Commands,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
-    mut lines: ResMut<DebugLines>,
-    mut spoken_events: EventReader<SpokenEvent>,
-    mut query: Query<(Entity, &Transform, &ID, &Brain, &mut Opinions)>,
-    sprites: Res<SpriteRegistry>,
-    face_directory: Res<FaceDirectory>,
-) {
-    for spoken_event in spoken_events.iter() {
-        for (entity, transform, id, _, mut opinions) in query.iter_mut() {
-            if spoken_event.origin.distance(transform.translation) <= spoken_event.distance
-                && id.0 != spoken_event.author
-            {
-                let texture = sprites.thought.clone();
+    /// The most liked people, as long as they are liked at all.
+    fn favorites(&self) -> impl Iterator<Item = &(String, f64)> {
+        self.people
+            .ranking()
+            .iter()
+            .take(RANKING_VIEW_SIZE)
+            .filter(|(_, likeability)| *likeability > 0.0)
+    }
 
-                let bubble = commands
-                    .spawn_bundle(SpriteBundle {
-                        texture: texture.clone(),
-                        transform: Transform {
-                            translation: Vec3::new(40.0, 45.0, 2.0),
-                            scale: Vec3::new(1.0, 1.0, 1.0),
-                            ..Default::default()
-                        },
-                        ..Default::default()
-                    })
-                    .insert(Lifetime(Timer::from_seconds(2.0, true)))
-                    .id();
+    /// The most disliked people, worst first.
+    fn rivals(&self) -> impl Iterator<Item = &(String, f64)> {
+        self.people
+            .ranking()
+            .iter()
+            .rev()
+            .take(RANKING_VIEW_SIZE)
+            .filter(|(_, likeability)| *likeability < 0.0)
+    }
 
-                if let Some((subject_id, personal_opinion)) = &spoken_event.opinion {
-                    let subject: String = subject_id.clone();
-                    let transmitted_opinion: PersonalOpinion = personal_opinion.clone();
+    /// Who turned the agent against the subject, speakers ordered by how badly they talked about it.
+    fn turned_against_by(&self, subject_id: &String) -> Vec<(String, f64)> {
+        let mut accusers: HashMap<&String, f64> = HashMap::new();
+        for testimony in self
+            .people
+            .get(subject_id)
+            .into_iter()
+            .flat_map(|opinion| &opinion.sources)
+        {
+            if testimony.value < 0.0 {
+                *accusers.entry(&testimony.speaker_id).or_insert(0.0) += testimony.value;
+            }
+        }
 
-                    process_heard_opinion(
-                        &mut opinions,
-                        &spoken_event.author,
-                        &subject,
-                        &transmitted_opinion,
-                    );
+        let mut accusers: Vec<(String, f64)> = accusers
+            .into_iter()
+            .map(|(speaker_id, total)| (speaker_id.clone(), total))
+            .collect();
+        accusers.sort_by(|a, b| a.1.total_cmp(&b.1));
 
-                    let face_texture = face_directory.faces.get(&subject);
+        accusers
+    }
 
-                    if let Some(handle) = face_texture {
-                        let face_handle = handle;
-                        let face_atlas = TextureAtlas::from_grid(
-                            face_handle.clone(),
-                            Vec2::new(45.0, 45.0),
-                            1,
-                            1,
-                        );
-                        let face_atlas_handle = texture_atlases.add(face_atlas);
-                        let gossip_subject_face = commands
-                            .spawn_bundle(SpriteSheetBundle {
-                                texture_atlas: face_atlas_handle,
-                                transform: Transform {
-                                    translation: Vec3::new(5.0, 0.0, 4.0),
-                                    scale: Vec3::new(0.7, 0.7, 1.0),
-                                    ..Default::default()
-                                },
-                                ..Default::default()
-                            })
-                            .id();
-                        commands
-                            .entity(bubble)
-                            .push_children(&[gossip_subject_face]);
-                    }
+    /// Standing of `person_id` as the agent perceives it, the mean of what others have told it about them, in -100..100.
+    /// None until someone besides the person themselves has spoken of them.
+    fn perceived_status(&self, person_id: &String) -> Option<f64> {
+        let (total, count) = self
+            .people
+            .get(person_id)?
+            .sources
+            .iter()
+            .filter(|testimony| {
+                testimony.speaker_id != self.owner_id && testimony.speaker_id != *person_id
+            })
+            .fold((0.0, 0), |(total, count), testimony| {
+                (total + testimony.value, count + 1)
+            });
 
-                    let mut start_color = Color::BLUE;
-                    let mut end_color = Color::GREEN;
-                    let mut value_icon_texture = sprites.thumbs_down.clone();
+        if count == 0 {
+            None
+        } else {
+            Some(total / count as f64)
+        }
+    }
 
-                    if transmitted_opinion.likeability > 0.0 {
-                        start_color = Color::RED;
-                        end_color = Color::ORANGE;
-                        value_icon_texture = sprites.thumbs_up.clone();
-                    }
+    /// Multiplier on how persuasive the person is and how eagerly they are sought out, in 0.5..1.5.
+    fn prestige(&self, person_id: &String) -> f64 {
+        1.0 + PRESTIGE_WEIGHT * self.perceived_status(person_id).unwrap_or(0.0) / 100.0
+    }
 
-                    let judgement = commands
-                        .spawn_bundle(SpriteBundle {
-                            texture: value_icon_texture,
-                            transform: Transform {
-                                translation: Vec3::new(-15.0, 3.0, 2.0),
-                                scale: Vec3::new(0.5, 0.5, 1.0),
-                                ..Default::default()
-                            },
-                            ..Default::default()
-                        })
-                        .id();
+    /// Likeability the agent holds of itself, -100..100.
+    fn self_esteem(&self) -> f64 {
+        self.people
+            .get(&self.owner_id)
+            .map_or(0.0, |opinion| opinion.likeability)
+    }
 
-                    commands.entity(bubble).push_children(&[judgement]);
+    /// Falls back to the owner itself when nobody is liked.
+    fn get_fav_person_id(&self) -> String {
+        self.favorites()
+            .next()
+            .map_or(self.owner_id.clone(), |(favorite_id, _)| {
+                favorite_id.clone()
+            })
+    }
 
-                    lines.line_gradient(
-                        spoken_event.origin,
-                        transform.translation,
-                        0.3,
-                        start_color,
-                        end_color,
-                    );
-                }
+    /// How worth talking about a person is, strong and fresh opinions come first,
+    /// people standing in the audience count extra and people of standing are talked about more.
+    fn topic_relevance(&self, person_id: &String, now: f64, audience: &[String]) -> f64 {
+        let salience = self
+            .people
+            .get(person_id)
+            .map_or(0.0, |opinion| opinion.salience(now));
+        let presence = if audience.contains(person_id) {
+            AUDIENCE_RELEVANCE
+        } else {
+            1.0
+        };
 
-                // Rendering head of the source model
-                //asset_server Load funcs should be in setup only, as they access filesystem.
-                let sprite_handle = &spoken_event.identity;
-                let texture_atlas =
-                    TextureAtlas::from_grid(sprite_handle.clone(), Vec2::new(45.0, 45.0), 1, 1);
-                let texture_atlas_handle = texture_atlases.add(texture_atlas);
-                let head = commands
-                    .spawn_bundle(SpriteSheetBundle {
-                        texture_atlas: texture_atlas_handle,
-                        transform: Transform {
-                            translation: Vec3::new(20.0, 20.0, 4.0),
-                            scale: Vec3::new(0.7, 0.7, 1.0),
-                            ..Default::default()
-                        },
-                        ..Default::default()
-                    })
-                    .id();
+        (1.0 + salience) * presence * self.prestige(person_id)
+    }
 
-                commands.entity(bubble).push_children(&[head]);
-                commands.entity(entity).push_children(&[bubble]);
+    /// Known people and topics the agent has a stance on sorted by relevance, most relevant first.
+    fn ranked_topics(&self, now: f64, audience: &[String]) -> Vec<Subject> {
+        let people = self.people.keys().map(|person_id| {
+            (
+                Subject::Person(person_id.clone()),
+                self.topic_relevance(person_id, now, audience),
+            )
+        });
+        let stances = self.stances.iter().map(|(topic_id, stance)| {
+            (Subject::Topic(topic_id.clone()), 1.0 + stance.salience(now))
+        });
+
+        let mut topics: Vec<(Subject, f64)> = people.chain(stances).collect();
+        topics.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        topics.into_iter().map(|(subject, _)| subject).collect()
+    }
+
+    /// Picks what to talk about as a weighted random draw over the topics ranked by relevance,
+    /// where the most relevant topic is `topic_bias` times more likely than the least relevant one.
+    /// Agents who know nobody but themselves and hold no stances have nothing to tell and ask around instead.
+    fn generate_speakable_personal_opinion(
+        &mut self,
+        transform: &Transform,
+        id: &ID,
+        identity: &Identity,
+        now: f64,
+        audience: &[String],
+        topic_bias: f64,
+    ) -> SpokenEvent {
+        let opinion = if self.people.len() <= 1 && self.stances.is_empty() {
+            None
+        } else {
+            let topics = self.ranked_topics(now, audience);
+            let subject = ranked_choice(&topics, topic_bias)
+                .cloned()
+                .unwrap_or(Subject::Person(id.0.clone()));
+
+            let opinion: PersonalOpinion = match &subject {
+                Subject::Person(person_id) => self.people.get(person_id),
+                Subject::Topic(topic_id) => self.stances.get(topic_id),
             }
-        }
+            .expect("Could not get opinion")
+            .clone();
+
+            Some((subject, opinion))
+        };
+
+        let event = SpokenEvent {
+            author: id.0.clone(),
+            origin: transform.translation,
+            distance: Register::Say.range(),
+            register: Register::Say,
+            listeners: audience.to_vec(),
+            identity: identity.0.clone(),
+            opinion,
+            confrontation: false,
+        };
+
+        event
+        //let opinion = opinions.people.get(person);
+    }
+
+    /// What the agent has to say about a person it picked itself, None when it has no opinion of them.
+    fn speak_about(
+        &self,
+        person_id: &String,
+        transform: &Transform,
+        id: &ID,
+        identity: &Identity,
+        audience: &[String],
+    ) -> Option<SpokenEvent> {
+        let opinion = self.people.get(person_id)?.clone();
+
+        Some(SpokenEvent {
+            author: id.0.clone(),
+            origin: transform.translation,
+            distance: Register::Say.range(),
+            register: Register::Say,
+            listeners: audience.to_vec(),
+            identity: identity.0.clone(),
+            opinion: Some((Subject::Person(person_id.clone()), opinion)),
+            confrontation: false,
+        })
+    }
+
+    /// Tells the speaker to its face what the agent thinks of it.
+    fn confront(
+        &self,
+        speaker_id: &String,
+        transform: &Transform,
+        id: &ID,
+        identity: &Identity,
+    ) -> Option<SpokenEvent> {
+        let mut event = self.speak_about(
+            speaker_id,
+            transform,
+            id,
+            identity,
+            std::slice::from_ref(speaker_id),
+        )?;
+        event.confrontation = true;
+
+        Some(event)
     }
 }
 
-fn lifetime_despawn_system(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut query: Query<(Entity, &mut Lifetime)>,
-) {
-    for (entity, mut auto_remove) in query.iter_mut() {
-        if auto_remove.0.tick(time.delta()).finished() {
-            commands.entity(entity).despawn_recursive();
+#[derive(Component, Clone, Debug)]
+pub struct PersonalOpinion {
+    trust_seed: f64,
+    pub trust: f64,
+    likeability_seed: f64,
+    pub likeability: f64,
+    /// Seeds set by the first impression, accumulated information slowly decays back towards them.
+    trust_baseline: f64,
+    likeability_baseline: f64,
+    /// Precision of the likeability belief, only grows under the Bayesian opinion model.
+    pub certainty: f64,
+    /// Simulation time the opinion was last formed or fed by gossip.
+    pub last_reinforced: f64,
+    /// Most recent claims that moved the opinion, oldest first.
+    pub sources: VecDeque<Testimony>,
+}
+
+/// A claim about someone, as remembered by the listener it was made to.
+#[derive(Clone, Debug)]
+pub struct Testimony {
+    pub speaker_id: String,
+    /// Simulation time the claim was heard.
+    pub heard_at: f64,
+    /// Likeability of the subject according to the speaker, -100..100.
+    pub value: f64,
+}
+
+impl PersonalOpinion {
+    /// Tethered adjust, trust moves fully and likeability follows at half strength.
+    fn adjust_trust(&mut self, modifier_value: f64) {
+        self.trust_seed = accumulate(self.trust_seed, self.trust_baseline, modifier_value);
+        self.likeability_seed = accumulate(
+            self.likeability_seed,
+            self.likeability_baseline,
+            0.5 * modifier_value,
+        );
+        self.propegate_output_values();
+    }
+
+    /// Tethered adjust, likeability moves fully and trust follows at half strength.
+    fn adjust_likeability(&mut self, modifier_value: f64) {
+        self.trust_seed = accumulate(self.trust_seed, self.trust_baseline, 0.5 * modifier_value);
+        self.likeability_seed = accumulate(
+            self.likeability_seed,
+            self.likeability_baseline,
+            modifier_value,
+        );
+        self.propegate_output_values();
+    }
+
+    fn reinforce(&mut self, now: f64) {
+        self.last_reinforced = now;
+    }
+
+    fn record_testimony(&mut self, testimony: Testimony) {
+        if self.sources.len() == PROVENANCE_LENGTH {
+            self.sources.pop_front();
         }
+        self.sources.push_back(testimony);
+    }
+
+    /// Earlier claims from other speakers that are at odds with the given value.
+    fn contradicting_sources<'a>(
+        &'a self,
+        speaker_id: &'a String,
+        value: f64,
+    ) -> impl Iterator<Item = &'a Testimony> {
+        self.sources.iter().filter(move |testimony| {
+            testimony.speaker_id != *speaker_id
+                && (testimony.value - value).abs() >= CONTRADICTION_MARGIN
+        })
+    }
+
+    /// Lets the whole opinion, first impression included, sink back towards neutral over `elapsed` seconds.
+    fn fade(&mut self, elapsed: f64) {
+        let factor = 0.5_f64.powf(elapsed / OPINION_HALF_LIFE);
+
+        self.trust_seed *= factor;
+        self.likeability_seed *= factor;
+        self.trust_baseline *= factor;
+        self.likeability_baseline *= factor;
+        self.propegate_output_values();
+    }
+
+    /// How much the person is on the agent's mind, strong feelings and fresh news both count.
+    fn salience(&self, now: f64) -> f64 {
+        let recency = 0.5_f64.powf((now - self.last_reinforced).max(0.0) / OPINION_HALF_LIFE);
+
+        self.likeability.abs().max(self.trust.abs()) * (0.5 + recency)
+    }
+
+    /// Sets the likeability output directly, for opinion models which work on opinions rather than seeds.
+    fn set_likeability(&mut self, value: f64) {
+        self.likeability_seed = logistic_seed(value);
+        self.propegate_output_values();
+    }
+
+    /// Logistic function provides high stability around extreme affection, and extreme dislike, but more variability while on the fence.
+    /// The degree to which variabiliy happens around origin is determined by const LOGISTIC_OPINION_SCALE.
+    fn propegate_output_values(&mut self) {
+        self.trust = logistic_opinion(self.trust_seed);
+        self.likeability = logistic_opinion(self.likeability_seed);
+    }
+
+    fn new(init_trust: f64, init_likeability: f64) -> Self {
+        let mut output_opinion = PersonalOpinion {
+            trust: 0.0,
+            likeability: 0.0,
+            trust_seed: init_trust,
+            likeability_seed: init_likeability,
+            trust_baseline: init_trust,
+            likeability_baseline: init_likeability,
+            certainty: 1.0,
+            last_reinforced: 0.0,
+            sources: VecDeque::new(),
+        };
+        output_opinion.propegate_output_values();
+
+        output_opinion
     }
 }
 
-// ============ SUBSYSTEMS ============
+/// Squashes an unbounded seed into an opinion in -100..100.
+fn logistic_opinion(seed: f64) -> f64 {
+    (200.0 / (1.0 + E.powf(LOGISTIC_OPINION_SCALE * seed))) - 100.0
+}
 
-fn process_heard_opinion(
-    listener_opinions: &mut Opinions,
-    speaker_id: &String,
-    subject_id: &String,
-    transmitted_opinion: &PersonalOpinion,
-) {
-    // let mut value: f64 = 0.0;
-    let mut held_opinion_of_speaker: f64 = 0.0;
-    let mut held_opinion_of_subject: f64 = 0.0;
-    let transmitted_value_judgement: f64 = transmitted_opinion.likeability.clone();
+/// Inverse of logistic_opinion, values are kept off the asymptotes.
+fn logistic_seed(value: f64) -> f64 {
+    let value = value.clamp(-99.99, 99.99);
+    (200.0 / (value + 100.0) - 1.0).ln() / LOGISTIC_OPINION_SCALE
+}
 
-    match (
-        listener_opinions.people.get(speaker_id),
-        listener_opinions.people.get(subject_id),
-    ) {
-        (Some(held_speaker_opinion), Some(held_subject_opinion)) => {
-            held_opinion_of_speaker = held_speaker_opinion.likeability.clone();
-            held_opinion_of_subject = held_subject_opinion.likeability.clone();
-        }
-        (Some(held_speaker_opinion), None) => {
-            held_opinion_of_speaker = held_speaker_opinion.likeability.clone();
-            listener_opinions
-                .people
-                .insert(subject_id.clone(), get_initial_impression());
+/// Pulls the seed a step back towards its baseline before adding the new information,
+/// so old news fades out and the seed settles instead of growing without bound.
+fn accumulate(seed: f64, baseline: f64, modifier_value: f64) -> f64 {
+    baseline + (seed - baseline) * (1.0 - OPINION_SEED_DECAY) + modifier_value
+}
+
+/// What an agent remembers about a place: who it ran into there, and how nice that company was.
+#[derive(Clone, Default)]
+pub struct LocationOpinion {
+    pub visits: u32,
+    /// Running average of the likeability of the company met there.
+    pub pleasantness: f64,
+    pub met: HashMap<String, u32>,
+}
+
+impl LocationOpinion {
+    fn record_visit(&mut self, met: &[(String, f64)]) {
+        self.visits += 1;
+
+        if met.is_empty() {
+            return;
         }
-        (None, Some(held_subject_opinion)) => {
-            held_opinion_of_subject = held_subject_opinion.likeability.clone();
-            listener_opinions
-                .people
-                .insert(subject_id.clone(), get_initial_impression());
+
+        let company: f64 =
+            met.iter().map(|(_, likeability)| likeability).sum::<f64>() / met.len() as f64;
+        self.pleasantness += LOCATION_MEMORY_RATE * (company - self.pleasantness);
+
+        for (person_id, _) in met {
+            *self.met.entry(person_id.clone()).or_insert(0) += 1;
         }
-        (None, None) => {
-            listener_opinions
-                .people
-                .insert(speaker_id.clone(), get_initial_impression());
-            listener_opinions
-                .people
-                .insert(subject_id.clone(), get_initial_impression());
+    }
+}
+
+// ============ EVENTS ============
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Register {
+    /// Private, only the addressed listeners hear it.
+    Whisper,
+    Say,
+    /// Public, heard across the square.
+    Announce,
+}
+
+impl Register {
+    fn range(&self) -> f32 {
+        match self {
+            Register::Whisper => WHISPER_RADIUS,
+            Register::Say => SPEECH_RADIUS,
+            Register::Announce => ANNOUNCE_RADIUS,
         }
     }
+}
 
-    if held_opinion_of_speaker > 0.0 {
-        let weight = 100.0 - held_opinion_of_speaker;
-        let op: &mut PersonalOpinion = listener_opinions
-            .people
-            .get_mut(&subject_id.clone())
-            .unwrap();
-        op.adjust_likeability(weight * transmitted_value_judgement);
-    } else {
-        let weight = -100.0 - held_opinion_of_speaker;
-        let op: &mut PersonalOpinion = listener_opinions.people.get_mut(subject_id).unwrap();
-        op.adjust_likeability(weight * transmitted_value_judgement);
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Distortion {
+    /// The opinion is made to sound stronger than it is.
+    Exaggeration,
+    /// Praise retold as criticism or the other way around.
+    SignFlip,
+    /// The opinion is pinned on someone else the speaker knows.
+    Misattribution,
+}
+
+/// How an agent answers overhearing itself being badmouthed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Retort {
+    /// Walk up to the speaker and set the record straight.
+    Confront,
+    /// Badmouth the speaker in turn.
+    Retaliate,
+}
+
+/// What a piece of speech is about.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Subject {
+    Person(String),
+    Topic(String),
+}
+
+struct SpokenEvent {
+    author: String,
+    origin: Vec3,
+    distance: f32,
+    register: Register,
+    /// Agents the speech was addressed to, anyone else in range merely overhears it.
+    listeners: Vec<String>,
+    identity: Handle<Image>,
+    opinion: Option<(Subject, PersonalOpinion)>,
+    /// Said to the listener's face in answer to its gossip, which is never confronted in turn.
+    confrontation: bool,
+}
+
+impl SpokenEvent {
+    /// What the speech bubble shows: the kind of topic brought up, whether a person is praised or run down,
+    /// or a question when the speaker has nothing to tell and is asking around.
+    fn icon(&self, topic_directory: &TopicDirectory) -> Icon {
+        match &self.opinion {
+            None => Icon::Question,
+            Some((Subject::Topic(topic_id), _)) => topic_directory
+                .get(topic_id)
+                .map_or(Icon::Question, |topic| topic.emote()),
+            Some((Subject::Person(_), opinion)) if opinion.likeability < 0.0 => Icon::Insult,
+            Some((Subject::Person(_), _)) => Icon::Praise,
+        }
     }
 
-    let subject_opinion = listener_opinions
-        .people
-        .get(&subject_id.clone())
-        .unwrap()
-        .clone();
-    listener_opinions.check_if_new_favorite(&subject_opinion, &subject_id);
+    /// The speech bubble of the event as said by a speaker in the given mood.
+    fn bubble(&self, topic_directory: &TopicDirectory, mood: Mood) -> SpeechBubble {
+        let tint = match &self.opinion {
+            Some((Subject::Topic(topic_id), _)) => topic_directory
+                .get(topic_id)
+                .map_or(Color::WHITE, |topic| topic.tint()),
+            _ => Color::WHITE,
+        };
 
-    fn get_initial_impression() -> PersonalOpinion {
-        let mut rng = rand::thread_rng();
-        let value: f64 = rng.gen_range(-50.0..50.0);
+        SpeechBubble {
+            content: self.icon(topic_directory),
+            tint,
+            mood: mood.emote(),
+        }
+    }
+
+    /// Lets the speaker's personality shape the retelling, deliberate lies first, then honest mistakes.
+    fn retold(mut self, personality: &Personality, opinions: &Opinions) -> Self {
+        if self.flattery_pays(opinions) && thread_rng().gen_bool(personality.dishonesty()) {
+            self.lie();
+        }
+        if let Some(distortion) = personality.distortion() {
+            self.distort(distortion, opinions);
+        }
+        self
+    }
+
+    /// The speaker dislikes the subject, but the subject is listening or a listener is known to speak well of it.
+    fn flattery_pays(&self, opinions: &Opinions) -> bool {
+        let (subject_id, opinion) = match &self.opinion {
+            Some((Subject::Person(subject_id), opinion)) => (subject_id, opinion),
+            _ => return false,
+        };
+        if opinion.likeability >= 0.0 || *subject_id == self.author {
+            return false;
+        }
+
+        self.listeners.contains(subject_id)
+            || opinions.people.get(subject_id).is_some_and(|held| {
+                held.sources.iter().any(|testimony| {
+                    testimony.value > 0.0 && self.listeners.contains(&testimony.speaker_id)
+                })
+            })
+    }
+
+    /// Praises the subject as much as the speaker actually dislikes it.
+    fn lie(&mut self) {
+        if let Some((_, opinion)) = &mut self.opinion {
+            opinion.set_likeability(-opinion.likeability);
+            debug!("{} lies", self.author);
+        }
+    }
+
+    fn distort(&mut self, distortion: Distortion, opinions: &Opinions) {
+        let (subject, opinion) = match &mut self.opinion {
+            Some(statement) => statement,
+            None => return,
+        };
+
+        match distortion {
+            Distortion::Exaggeration => opinion.set_likeability(opinion.likeability * EXAGGERATION),
+            Distortion::SignFlip => opinion.set_likeability(-opinion.likeability),
+            Distortion::Misattribution => {
+                if let Subject::Person(subject_id) = subject {
+                    let scapegoat = opinions
+                        .people
+                        .keys()
+                        .filter(|person_id| {
+                            **person_id != *subject_id && **person_id != self.author
+                        })
+                        .choose(&mut rand::thread_rng());
+                    if let Some(scapegoat) = scapegoat {
+                        *subject_id = scapegoat.clone();
+                    }
+                }
+            }
+        }
+        debug!("{} retold with {:?}", self.author, distortion);
+    }
+
+    fn in_register(mut self, register: Register) -> Self {
+        self.register = register;
+        self.distance = register.range();
+        self
+    }
+
+    /// Whether an agent at `position` hears the speech, `hearing_boost` extends the range for attentive listeners.
+    fn reaches(&self, listener_id: &String, position: Vec3, hearing_boost: f32) -> bool {
+        if *listener_id == self.author {
+            return false;
+        }
+        if self.listeners.contains(listener_id) {
+            return true;
+        }
+
+        self.register != Register::Whisper
+            && self.origin.distance(position) <= self.distance * hearing_boost
+    }
+}
+// ============ SYSTEM LABELS ============
+
+#[derive(SystemLabel, Clone, Hash, Debug, Eq, PartialEq)]
+enum StartupLabels {
+    LoadSprites,
+    PopulateSim,
+}
+
+#[derive(SystemLabel, Clone, Hash, Debug, Eq, PartialEq)]
+enum SimLabels {
+    Steering,
+    Physics,
+    Gatherings,
+}
+
+// ============ PLUGIN ============
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        let args = app
+            .world
+            .get_resource::<Args>()
+            .expect("Args should be inserted before SimulationPlugin");
+        let steering_weights = SteeringWeights::from(args);
+        let personality_distribution = match &args.personality_file {
+            Some(path) => PersonalityDistribution::load(path).unwrap_or_else(|e| {
+                error!(
+                    "Could not load personality distribution, using defaults: {}",
+                    e
+                );
+                PersonalityDistribution::default()
+            }),
+            None => PersonalityDistribution::default(),
+        };
+        let topic_directory = match &args.topics_file {
+            Some(path) => TopicDirectory::load(path).unwrap_or_else(|e| {
+                error!("Could not load topics, gossiping about people only: {}", e);
+                TopicDirectory::default()
+            }),
+            None => TopicDirectory::default(),
+        };
+        let faction_directory = match &args.faction_file {
+            Some(path) => FactionDirectory::load(path).unwrap_or_else(|e| {
+                error!("Could not load factions, starting without any: {}", e);
+                FactionDirectory::default()
+            }),
+            None => FactionDirectory::default(),
+        };
+        let opinion_dynamics = OpinionDynamics {
+            model: args.opinion_model.into(),
+        };
+        info!("Opinion model: {}", opinion_dynamics.model.name());
+
+        app.add_event::<SpokenEvent>()
+            .insert_resource(steering_weights)
+            .insert_resource(personality_distribution)
+            .insert_resource(opinion_dynamics)
+            .insert_resource(topic_directory)
+            .insert_resource(faction_directory)
+            .init_resource::<SimulationClock>()
+            .init_resource::<BeliefDrift>()
+            .init_resource::<Reputation>()
+            .init_resource::<FactionMetrics>()
+            .init_resource::<TransformState>()
+            .init_resource::<FaceDirectory>()
+            .init_resource::<SpriteRegistry>()
+            .init_resource::<LocationDirectory>()
+            .register_type::<Goal>()
+            .add_startup_system(load_sprites_startup.label(StartupLabels::LoadSprites))
+            .add_startup_system(setup_startup)
+            .add_startup_system(reputation_hud_startup)
+            .add_startup_system(spawn_locations_startup)
+            .add_startup_system(
+                populate_sim_startup
+                    .label(StartupLabels::PopulateSim)
+                    .after(StartupLabels::LoadSprites),
+            )
+            .add_startup_system(report_agent_transform_system.after(StartupLabels::PopulateSim))
+            .add_startup_system_to_stage(StartupStage::PostStartup, build_network_startup)
+            .add_startup_system_to_stage(StartupStage::PostStartup, attach_behavior_startup)
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(0.1))
+                    .with_system(animate_sprite_system)
+                    .with_system(executive_functioning_system)
+                    .with_system(behavior_tree_system),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(0.2))
+                    .with_system(say_system)
+                    .with_system(strike_up_conversation_system)
+                    .with_system(conversation_system)
+                    .with_system(confrontation_system)
+                    .with_system(retaliation_system),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(MEMORY_TICK))
+                    .with_system(location_memory_system)
+                    .with_system(memory_decay_system),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(GATHERING_TICK))
+                    .with_system(gathering_system.label(SimLabels::Gatherings))
+                    // new members only get their Attending component at the end of the stage
+                    .with_system(gathering_formation_system.after(SimLabels::Gatherings)),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(DRIFT_REPORT_INTERVAL))
+                    .with_system(belief_drift_system)
+                    .with_system(faction_metrics_system),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(REPUTATION_TICK))
+                    .with_system(reputation_system),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(ENCOUNTER_TICK))
+                    .with_system(encounter_system),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(0.01))
+                    .with_system(report_agent_transform_system),
+            )
+            .add_system_to_stage(CoreStage::PreUpdate, clock_system)
+            .add_system(steering_system.label(SimLabels::Steering))
+            .add_system(
+                physics_system
+                    .label(SimLabels::Physics)
+                    .after(SimLabels::Steering),
+            )
+            .add_system(boundaries_system.after(SimLabels::Physics))
+            .add_system(direct_sprite_system)
+            .add_system(faction_tint_system)
+            .add_system(emotion_system)
+            .add_system(emote_system)
+            .add_system(reputation_hud_system)
+            .add_system(thought_system)
+            .add_system(lifetime_despawn_system);
+    }
+}
+
+// ============ STARTUP SYSTEMS ============
+
+fn setup_startup(mut commands: Commands) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    commands.spawn_bundle(UiCameraBundle::default());
+}
+
+fn reputation_hud_startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(10.0),
+                    left: Val::Px(10.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "Reputation",
+                TextStyle {
+                    font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+                    font_size: 16.0,
+                    color: Color::WHITE,
+                },
+                Default::default(),
+            ),
+            ..Default::default()
+        })
+        .insert(ReputationHud);
+}
+
+fn spawn_locations_startup(
+    mut commands: Commands,
+    mut location_directory: ResMut<LocationDirectory>,
+) {
+    info!("Spawning locations");
+    let locations = [
+        (
+            "plaza",
+            Vec3::new(0.0, 0.0, 0.0),
+            120.0,
+            Color::rgba(0.9, 0.8, 0.5, 0.15),
+        ),
+        (
+            "cafe",
+            Vec3::new(-450.0, 230.0, 0.0),
+            90.0,
+            Color::rgba(0.6, 0.4, 0.2, 0.15),
+        ),
+        (
+            "park",
+            Vec3::new(450.0, -220.0, 0.0),
+            150.0,
+            Color::rgba(0.2, 0.8, 0.3, 0.15),
+        ),
+        (
+            "library",
+            Vec3::new(450.0, 250.0, 0.0),
+            80.0,
+            Color::rgba(0.3, 0.4, 0.9, 0.15),
+        ),
+    ];
+
+    for (name, center, radius, color) in locations {
+        commands.spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::splat(radius * 2.0)),
+                ..Default::default()
+            },
+            transform: Transform::from_translation(center),
+            ..Default::default()
+        });
+
+        location_directory
+            .locations
+            .insert(name.to_string(), LocationZone { center, radius });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn populate_sim_startup(
+    mut commands: Commands,
+    sprites: Res<SpriteRegistry>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut face_directory: ResMut<FaceDirectory>,
+    personality_distribution: Res<PersonalityDistribution>,
+    topic_directory: Res<TopicDirectory>,
+    mut faction_directory: ResMut<FactionDirectory>,
+    args: Res<Args>,
+) {
+    info!("Populating simulation");
+    for index in 0..args.population {
+        make_rand_character(
+            &mut commands,
+            SpawnIndex(index),
+            &sprites,
+            &mut texture_atlases,
+            &mut face_directory,
+            &personality_distribution,
+            &topic_directory,
+            &mut faction_directory,
+        )
+    }
+}
+
+fn load_sprites_startup(
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut sprite_registry: ResMut<SpriteRegistry>,
+) {
+    info!("Loading population sprites");
+    let sprites = vec![
+        "lady.png",
+        "baldguy.png",
+        "coollady.png",
+        "princessleia.png",
+        "blondedude.png",
+        "hatguy.png",
+        "redhead.png",
+        "jacketguy.png",
+    ];
+
+    let sprites_map: HashMap<String, Handle<Image>> = sprites
+        .iter()
+        .map(|sprite| {
+            let sprite_handle: Handle<Image> = asset_server.load(sprite.to_owned());
+            (sprite.to_string(), sprite_handle)
+        })
+        .collect();
+
+    sprite_registry.characters = sprites_map;
+
+    info!("Loading bubble sprites");
+    sprite_registry.thought = asset_server.load("thoughtbubble.png");
+    sprite_registry.speech = asset_server.load("textbubble.png");
+
+    info!("Loading sentiment sprites");
+    sprite_registry.thumbs_up = asset_server.load("good_thumbs_up.png");
+    sprite_registry.thumbs_down = asset_server.load("bad_thumbs_down.png");
+
+    info!("Loading emote sprites");
+    let emotes = TextureAtlas::from_grid(
+        asset_server.load("icons.png"),
+        Vec2::new(48.0, 48.0),
+        EMOTE_COLUMNS,
+        1,
+    );
+    sprite_registry.emotes = texture_atlases.add(emotes);
+}
+
+fn attach_behavior_startup(
+    mut commands: Commands,
+    args: Res<Args>,
+    query: Query<Entity, With<Brain>>,
+) {
+    let path = match &args.behavior_file {
+        Some(path) => path,
+        None => return,
+    };
+
+    match BehaviorTree::load(path) {
+        Ok(root) => {
+            info!("Attaching behavior tree {}", path);
+            for entity in query.iter() {
+                commands.entity(entity).insert(BehaviorTree {
+                    root: root.clone(),
+                    listening: false,
+                });
+            }
+        }
+        Err(e) => error!(
+            "Could not load behavior tree, falling back to built in behavior: {}",
+            e
+        ),
+    }
+}
+
+/// Ties agents together according to the network file or generated model, runs once the population is spawned.
+fn build_network_startup(
+    mut query: Query<(&SpawnIndex, &ID, &mut Opinions, &mut Factions)>,
+    mut faction_directory: ResMut<FactionDirectory>,
+    args: Res<Args>,
+) {
+    let population = query.iter().count();
+    let generate = || {
+        Network::generate(
+            &NetworkConfig::from(&*args),
+            population,
+            &mut rand::thread_rng(),
+        )
+    };
+    let network = match &args.network_file {
+        Some(path) => Network::load(path).unwrap_or_else(|e| {
+            error!("Could not load network, generating one instead: {}", e);
+            generate()
+        }),
+        None => generate(),
+    };
+    info!("Building network of {} ties", network.ties.len());
+
+    // network nodes are agents' spawn indices
+    let mut agents: Vec<(&SpawnIndex, &ID, Mut<Opinions>, Mut<Factions>)> =
+        query.iter_mut().collect();
+    agents.sort_by_key(|(spawn_index, ..)| spawn_index.0);
+
+    // the blocks of a generated block model become factions of their own
+    if args.network_file.is_none() && args.network == NetworkKind::StochasticBlock {
+        let blocks = args.sbm_blocks.max(1);
+        for block in 0..blocks {
+            faction_directory.factions.push(FactionDefinition {
+                id: format!("block-{}", block),
+                kind: FactionKind::Team,
+                share: 1.0 / blocks as f64,
+                color: FACTION_PALETTE[block % FACTION_PALETTE.len()],
+            });
+        }
+
+        for (index, (_, id, _, factions)) in agents.iter_mut().enumerate() {
+            let faction_id = format!("block-{}", network::faction_of(index, blocks));
+            faction_directory.join(&id.0, &faction_id);
+            factions.0.push(faction_id);
+        }
+    }
+
+    for (a, b, tie) in network.ties {
+        if a == b || a >= population || b >= population {
+            warn!("Skipping tie between agents {} and {}", a, b);
+            continue;
+        }
+
+        let opinion = match tie {
+            Tie::Friend => PersonalOpinion::new(100.0, 100.0),
+            Tie::Rival => PersonalOpinion::new(-100.0, -100.0),
+        };
+        let (a_id, b_id) = (agents[a].1 .0.clone(), agents[b].1 .0.clone());
+        agents[a].2.insert_opinion(b_id, opinion.clone());
+        agents[b].2.insert_opinion(a_id, opinion);
+    }
+}
+
+// ============ SYSTEMS ============
+
+fn animate_sprite_system(mut query: Query<(&mut TextureAtlasSprite, &Body, &Direction)>) {
+    for (mut sprite, body, direction) in query.iter_mut() {
+        if !body.is_moving() {
+            continue;
+        }
+        let row = direction.spritesheet_row();
+        sprite.index = (row * 3 as usize) + (sprite.index + 1) % 3;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn make_rand_character(
+    commands: &mut Commands,
+    spawn_index: SpawnIndex,
+    sprites: &Res<SpriteRegistry>,
+    texture_atlases: &mut ResMut<Assets<TextureAtlas>>,
+    face_directory: &mut ResMut<FaceDirectory>,
+    personality_distribution: &PersonalityDistribution,
+    topic_directory: &TopicDirectory,
+    faction_directory: &mut FactionDirectory,
+) {
+    let sprite = sprites.random_character();
+    let sprite_handle: Handle<Image> = sprites.get_character(&sprite);
+
+    let texture_atlas = TextureAtlas::from_grid(sprite_handle.clone(), Vec2::new(52.0, 72.0), 3, 4);
+    let texture_atlas_handle = texture_atlases.add(texture_atlas);
+
+    let id = ID::rand();
+    let num_name = id.0.clone();
+
+    face_directory
+        .faces
+        .insert(num_name.clone(), sprite_handle.clone());
+
+    let personality = Personality::random(personality_distribution);
+    let body = Body::new(
+        personality.preferred_speed(),
+        rand::thread_rng().gen_range(0.8..1.2),
+    );
+
+    let mut rng = rand::thread_rng();
+    let initial_location: (f32, f32) = (rng.gen_range(-200.0..200.0), rng.gen_range(-200.0..200.0));
+
+    let mut opinions = Opinions::new(num_name.clone(), &personality);
+    for topic in topic_directory.topics.iter() {
+        if rng.gen_bool(topic.adoption.clamp(0.0, 1.0)) {
+            opinions.adopt(topic);
+        }
+    }
+
+    let joined: Vec<String> = faction_directory
+        .factions
+        .iter()
+        .filter(|faction| rng.gen_bool(faction.share.clamp(0.0, 1.0)))
+        .map(|faction| faction.id.clone())
+        .collect();
+    for faction_id in joined.iter() {
+        faction_directory.join(&num_name, faction_id);
+    }
+
+    commands
+        .spawn_bundle(SpriteSheetBundle {
+            texture_atlas: texture_atlas_handle,
+            transform: Transform {
+                translation: Vec3::new(initial_location.0, initial_location.1, 1.0),
+                scale: Vec3::new(1.0, 1.0, 1.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(id)
+        .insert(spawn_index)
+        .insert(Identity(sprite_handle.clone()))
+        .insert(body)
+        .insert(Direction::Right)
+        .insert(Voice)
+        .insert(opinions)
+        .insert(Factions(joined))
+        .insert(personality)
+        .insert(Emotion::default())
+        .insert(Brain::default())
+        .insert(Goal::default())
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(SpriteSheetBundle {
+                    texture_atlas: sprites.emotes.clone(),
+                    transform: Transform {
+                        translation: Vec3::new(-25.0, 45.0, 3.0),
+                        scale: Vec3::new(0.5, 0.5, 1.0),
+                        ..Default::default()
+                    },
+                    visibility: Visibility { is_visible: false },
+                    ..Default::default()
+                })
+                .insert(Emote);
+        });
+}
+
+/// Agents occasionally deliberate over a new goal, in between the target of goals following a moving person is kept up to date.
+#[allow(clippy::type_complexity)]
+fn executive_functioning_system(
+    mut query: Query<
+        (
+            &ID,
+            &mut Body,
+            &mut Brain,
+            &mut Goal,
+            &Personality,
+            &Opinions,
+            &Transform,
+        ),
+        (
+            With<Direction>,
+            Without<BehaviorTree>,
+            Without<Conversation>,
+            Without<Attending>,
+            Without<Confronting>,
+        ),
+    >,
+    transform_state: Res<TransformState>,
+    location_directory: Res<LocationDirectory>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for (id, mut body, mut brain, mut goal, personality, opinions, transform) in query.iter_mut() {
+        let actor_translation: Vec3 = transform.translation;
+        let should_deliberate: usize = rng.gen_range(0..100);
+
+        if should_deliberate <= DELIBERATION_CHANCE {
+            let new_goal = brain.deliberate(
+                id,
+                actor_translation,
+                personality,
+                opinions,
+                &transform_state,
+                &location_directory,
+            );
+
+            body.target = match &new_goal {
+                Goal::Wander => {
+                    let k: f64 = rng.gen_range(0.0..2000.0);
+                    let rads = k / 1000.0 * PI;
+                    let heading = Vec3::new(rads.cos() as f32, rads.sin() as f32, 0.0);
+                    Some(actor_translation + heading * WANDER_DISTANCE)
+                }
+                Goal::GoToLocation(location_id) => location_directory
+                    .get(location_id)
+                    .map(|zone| zone.random_point()),
+                Goal::JoinConversation(center) => Some(*center),
+                Goal::Rest => Some(actor_translation),
+                Goal::SeekFriend(_) | Goal::AvoidRival(_) => body.target,
+            };
+
+            if *goal != new_goal {
+                *goal = new_goal;
+            }
+        }
+
+        match &*goal {
+            Goal::SeekFriend(person_id) => {
+                if let Some(target_transform) = transform_state.get(person_id) {
+                    body.target = Some(target_transform.translation);
+                }
+            }
+            Goal::AvoidRival(person_id) => {
+                if let Some(target_transform) = transform_state.get(person_id) {
+                    let away =
+                        (actor_translation - target_transform.translation).normalize_or_zero();
+                    body.target = Some(actor_translation + away * WANDER_DISTANCE);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Ticks the behavior tree of data driven agents and carries out the intents it produced.
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+fn behavior_tree_system(
+    mut commands: Commands,
+    mut spoken_events: EventWriter<SpokenEvent>,
+    sprites: Res<SpriteRegistry>,
+    topic_directory: Res<TopicDirectory>,
+    transform_state: Res<TransformState>,
+    location_directory: Res<LocationDirectory>,
+    clock: Res<SimulationClock>,
+    args: Res<Args>,
+    mut query: Query<
+        (
+            Entity,
+            &ID,
+            &Identity,
+            &Transform,
+            &Personality,
+            &Emotion,
+            &mut Opinions,
+            &mut Body,
+            &mut BehaviorTree,
+        ),
+        Without<Confronting>,
+    >,
+) {
+    let mut intents = Vec::new();
+
+    for (entity, id, identity, transform, personality, emotion, mut opinions, mut body, mut tree) in
+        query.iter_mut()
+    {
+        intents.clear();
+
+        let blackboard = Blackboard {
+            id,
+            position: transform.translation,
+            target: body.target,
+            personality,
+            opinions: &opinions,
+            transform_state: &transform_state,
+            location_directory: &location_directory,
+        };
+        tree.root.tick(&blackboard, &mut intents);
+
+        tree.listening = false;
+
+        for intent in intents.iter() {
+            match intent {
+                Intent::MoveTo(point) => body.target = Some(*point),
+                Intent::Idle => body.target = Some(transform.translation),
+                Intent::Listen => {
+                    body.target = Some(transform.translation);
+                    tree.listening = true;
+                }
+                Intent::Speak => {
+                    let audience =
+                        audience(id, transform.translation, Register::Say, &transform_state);
+                    let event = opinions
+                        .generate_speakable_personal_opinion(
+                            transform,
+                            id,
+                            identity,
+                            clock.elapsed,
+                            &audience,
+                            args.topic_bias,
+                        )
+                        .retold(personality, &opinions);
+                    spawn_speech_bubble(
+                        &mut commands,
+                        &sprites,
+                        entity,
+                        event.bubble(&topic_directory, emotion.mood()),
+                    );
+                    spoken_events.send(event);
+                }
+            }
+        }
+    }
+}
+
+/// Agents standing inside a location remember who else was there and how they feel about them.
+fn location_memory_system(
+    mut query: Query<(&ID, &Transform, &mut Opinions)>,
+    transform_state: Res<TransformState>,
+    location_directory: Res<LocationDirectory>,
+) {
+    for (id, transform, mut opinions) in query.iter_mut() {
+        let location_id = match location_directory.location_at(transform.translation) {
+            Some(location_id) => location_id.clone(),
+            None => continue,
+        };
+        let zone = location_directory.get(&location_id).unwrap();
+
+        let met: Vec<(String, f64)> = transform_state
+            .transforms
+            .iter()
+            .filter(|(other_id, other_transform)| {
+                **other_id != id.0 && zone.contains(other_transform.translation)
+            })
+            .filter_map(|(other_id, _)| {
+                opinions
+                    .people
+                    .get(other_id)
+                    .map(|opinion| (other_id.clone(), opinion.likeability))
+            })
+            .collect();
+
+        opinions
+            .locations
+            .entry(location_id)
+            .or_default()
+            .record_visit(&met);
+    }
+}
+
+/// Compares every belief about another agent with that agent's merit and reports the average drift.
+fn belief_drift_system(
+    query: Query<(&ID, &Personality, &Opinions)>,
+    mut belief_drift: ResMut<BeliefDrift>,
+) {
+    let merits: HashMap<String, f64> = query
+        .iter()
+        .map(|(id, personality, _)| (id.0.clone(), personality.merit()))
+        .collect();
+
+    belief_drift.error = mean_belief_error(&merits, query.iter().map(|(.., opinions)| opinions));
+    if let Some(error) = belief_drift.error {
+        info!("Belief drift from ground truth: {:.1}", error);
+    }
+}
+
+/// Agents bumping into each other have a short interaction, its outcome shifts both of their opinions of each other
+/// and counts as firsthand experience, weighing more than anything heard through gossip.
+fn encounter_system(
+    mut query: Query<(
+        &ID,
+        &Identity,
+        &Transform,
+        &Personality,
+        &Factions,
+        &mut Opinions,
+    )>,
+    face_directory: Res<FaceDirectory>,
+    faction_directory: Res<FactionDirectory>,
+    opinion_dynamics: Res<OpinionDynamics>,
+    clock: Res<SimulationClock>,
+) {
+    let mut rng = rand::thread_rng();
+    let mut pairs = query.iter_combinations_mut();
+
+    while let Some([a, b]) = pairs.fetch_next() {
+        let (a_id, a_identity, a_transform, a_personality, a_factions, mut a_opinions) = a;
+        let (b_id, b_identity, b_transform, b_personality, b_factions, mut b_opinions) = b;
+
+        if a_transform.translation.distance(b_transform.translation) > ENCOUNTER_RADIUS
+            || !rng.gen_bool(ENCOUNTER_CHANCE)
+        {
+            continue;
+        }
+
+        for (opinions, personality, identity, factions, other_id, other_personality) in [
+            (
+                &mut a_opinions,
+                a_personality,
+                a_identity,
+                a_factions,
+                b_id,
+                b_personality,
+            ),
+            (
+                &mut b_opinions,
+                b_personality,
+                b_identity,
+                b_factions,
+                a_id,
+                a_personality,
+            ),
+        ] {
+            let outcome = (personality.rapport(other_personality) + rng.gen_range(-30.0..30.0))
+                .clamp(-100.0, 100.0);
+            let experiencer = Listener {
+                personality,
+                appearance: &identity.0,
+                face_directory: &face_directory,
+                factions,
+                faction_directory: &faction_directory,
+            };
+
+            process_encounter(
+                opinions,
+                &experiencer,
+                opinion_dynamics.model.as_ref(),
+                &other_id.0,
+                outcome,
+                clock.elapsed,
+            );
+        }
+    }
+}
+
+/// Members wear the color of their first faction, agents outside any faction keep their own colors.
+fn faction_tint_system(
+    mut query: Query<(&Factions, &mut TextureAtlasSprite), Changed<Factions>>,
+    faction_directory: Res<FactionDirectory>,
+) {
+    for (factions, mut sprite) in query.iter_mut() {
+        sprite.color = match factions.0.first().and_then(|id| faction_directory.get(id)) {
+            Some(faction) => {
+                let (r, g, b) = faction.color;
+                Color::rgb(0.5 + r / 2.0, 0.5 + g / 2.0, 0.5 + b / 2.0)
+            }
+            None => Color::WHITE,
+        };
+    }
+}
+
+fn reputation_system(query: Query<&Opinions>, mut reputation: ResMut<Reputation>) {
+    reputation.ranking = reputation_ranking(query.iter());
+}
+
+fn reputation_hud_system(
+    reputation: Res<Reputation>,
+    mut query: Query<&mut Text, With<ReputationHud>>,
+) {
+    if !reputation.is_changed() {
+        return;
+    }
+
+    let mut lines = vec!["Reputation".to_string()];
+    for (rank, (person_id, score)) in reputation.ranking.iter().take(HUD_RANKS).enumerate() {
+        let short_id: String = person_id.chars().take(8).collect();
+        lines.push(format!("{}. {} {:+.0}", rank + 1, short_id, score));
+    }
+
+    for mut text in query.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+fn faction_metrics_system(
+    query: Query<(&Factions, &Opinions)>,
+    faction_directory: Res<FactionDirectory>,
+    mut faction_metrics: ResMut<FactionMetrics>,
+) {
+    faction_metrics.statistics = faction_statistics(&faction_directory, query.iter());
+
+    for statistics in faction_metrics.statistics.iter() {
+        info!(
+            "Faction {}: {} members, in-group {:.1}, out-group {:.1}",
+            statistics.faction_id,
+            statistics.members,
+            statistics.in_group.unwrap_or(f64::NAN),
+            statistics.out_group.unwrap_or(f64::NAN)
+        );
+    }
+}
+
+fn memory_decay_system(
+    mut query: Query<&mut Opinions>,
+    clock: Res<SimulationClock>,
+    args: Res<Args>,
+) {
+    for mut opinions in query.iter_mut() {
+        opinions.fade(MEMORY_TICK);
+        opinions.forget_beyond(args.memory_capacity, clock.elapsed);
+    }
+}
+
+fn emotion_system(mut query: Query<&mut Emotion>, clock: Res<SimulationClock>) {
+    for mut emotion in query.iter_mut() {
+        emotion.settle(clock.delta as f64);
+    }
+}
+
+fn emote_system(
+    agents: Query<(&Emotion, &Children)>,
+    mut emotes: Query<(&mut TextureAtlasSprite, &mut Visibility), With<Emote>>,
+) {
+    for (emotion, children) in agents.iter() {
+        let emote = emotion.mood().emote();
+
+        for child in children.iter() {
+            if let Ok((mut sprite, mut visibility)) = emotes.get_mut(*child) {
+                visibility.is_visible = emote.is_some();
+                if let Some(icon) = emote {
+                    sprite.index = icon.index();
+                }
+            }
+        }
+    }
+}
+
+fn direct_sprite_system(
+    mut query: Query<(&Body, &mut Direction), (Changed<Body>, With<Transform>)>,
+) {
+    for (body, mut direction) in query.iter_mut() {
+        let x = body.velocity.x;
+        let y = body.velocity.y;
+
+        *direction = if x.abs() > y.abs() {
+            if x < 0.0 {
+                Direction::Left
+            } else {
+                Direction::Right
+            }
+        } else {
+            if y < 0.0 {
+                Direction::Down
+            } else {
+                Direction::Up
+            }
+        };
+
+        debug!("vel -> dir => {:?} -> {:?}", body.velocity, direction);
+    }
+}
+
+/// Sums the steering behaviors into a single force which nudges the current velocity:
+/// arrival towards the target, separation from anyone too close,
+/// avoidance of disliked agents and cohesion towards liked ones within perception range.
+fn steering_system(
+    mut query: Query<(&mut Body, &Transform, &ID, &Opinions)>,
+    transform_state: Res<TransformState>,
+    weights: Res<SteeringWeights>,
+) {
+    for (mut body, transform, id, opinions) in query.iter_mut() {
+        let position = transform.translation;
+
+        let seek = match body.target {
+            Some(target) => body.arrive(position, target),
+            None => Vec3::ZERO,
+        };
+
+        let mut separation = Vec3::ZERO;
+        let mut avoidance = Vec3::ZERO;
+        let mut liked_center = Vec3::ZERO;
+        let mut liked_count = 0;
+
+        for (other_id, other_transform) in transform_state.transforms.iter() {
+            if *other_id == id.0 {
+                continue;
+            }
+
+            let offset = position - other_transform.translation;
+            let distance = offset.length();
+
+            if distance > PERCEPTION_RADIUS {
+                continue;
+            }
+
+            if distance < SEPARATION_RADIUS {
+                let away = if distance > 0.0 {
+                    offset / distance
+                } else {
+                    Vec3::X
+                };
+                separation += away * (SEPARATION_RADIUS - distance) / SEPARATION_RADIUS;
+            }
+
+            if let Some(opinion) = opinions.people.get(other_id) {
+                if opinion.likeability < AVOIDANCE_LIKEABILITY && distance > 0.0 {
+                    let dislike = (opinion.likeability.abs() / 100.0) as f32;
+                    avoidance += offset / distance * dislike;
+                } else if opinion.likeability > COHESION_LIKEABILITY {
+                    liked_center += other_transform.translation;
+                    liked_count += 1;
+                }
+            }
+        }
+
+        let cohesion = if liked_count > 0 {
+            body.seek(position, liked_center / liked_count as f32)
+        } else {
+            Vec3::ZERO
+        };
+
+        let velocity_change = seek * weights.seek
+            + body.steer_towards(separation) * weights.separation
+            + body.steer_towards(avoidance) * weights.avoidance
+            + cohesion * weights.cohesion;
+
+        body.apply_force(velocity_change / STEERING_RESPONSE_TIME);
+    }
+}
+
+/// Semi-implicit Euler integration over the simulation clock's delta.
+fn physics_system(
+    mut query: Query<(&mut Body, &mut Transform, Option<&Emotion>)>,
+    clock: Res<SimulationClock>,
+) {
+    for (mut body, mut transform, emotion) in query.iter_mut() {
+        let max_speed = body.max_speed * emotion.map_or(1.0, |emotion| emotion.speed());
+        body.integrate(&mut transform.translation, max_speed, clock.delta);
+    }
+}
+
+fn report_agent_transform_system(
+    mut query: Query<(&Transform, &Body, &ID), With<Brain>>,
+    mut transform_global_state: ResMut<TransformState>,
+) {
+    for (transform, body, id) in query.iter_mut() {
+        transform_global_state
+            .transforms
+            .insert(id.0.clone(), *transform);
+    }
+}
+
+fn boundaries_system(mut query: Query<(&mut Body, &Transform), With<Direction>>) {
+    for (mut body, transform) in query.iter_mut() {
+        //these should be on window size, not manually hard-coded
+        let bounds = (
+            transform.translation.y > 400.0,
+            transform.translation.y < -400.0,
+            transform.translation.x > 700.0,
+            transform.translation.x < -700.0,
+        );
+
+        let inwards = match bounds {
+            (true, _, _, _) => -Vec3::Y,
+            (_, true, _, _) => Vec3::Y,
+            (_, _, true, _) => -Vec3::X,
+            (_, _, _, true) => Vec3::X,
+            _ => continue,
+        };
+
+        body.velocity = inwards * body.max_speed;
+        body.acceleration = Vec3::ZERO;
+
+        // drop whatever pulled the agent out, executive functioning will pick a new target
+        body.target = None;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn say_system(
+    mut commands: Commands,
+    mut spoken_events: EventWriter<SpokenEvent>,
+    sprites: Res<SpriteRegistry>,
+    topic_directory: Res<TopicDirectory>,
+    transform_state: Res<TransformState>,
+    clock: Res<SimulationClock>,
+    args: Res<Args>,
+    mut query: Query<
+        (
+            Entity,
+            &ID,
+            &Identity,
+            &Transform,
+            &Personality,
+            &Emotion,
+            &Voice,
+            &mut Opinions,
+        ),
+        (
+            Without<BehaviorTree>,
+            Without<Conversation>,
+            Without<Attending>,
+        ),
+    >,
+) {
+    let mut rng = rand::thread_rng();
+
+    for (entity, id, identity, transform, personality, emotion, _, mut opinions) in query.iter_mut()
+    {
+        let should_think: usize = rng.gen_range(0..10000);
+
+        if should_think as f64 <= personality.chattiness() as f64 * emotion.chattiness() {
+            let register = personality.speaking_register();
+            let audience = audience(id, transform.translation, register, &transform_state);
+            let event = opinions
+                .generate_speakable_personal_opinion(
+                    &transform,
+                    &id,
+                    &identity,
+                    clock.elapsed,
+                    &audience,
+                    args.topic_bias,
+                )
+                .retold(personality, &opinions)
+                .in_register(register);
+            spawn_speech_bubble(
+                &mut commands,
+                &sprites,
+                entity,
+                event.bubble(&topic_directory, emotion.mood()),
+            );
+            spoken_events.send(event);
+        }
+    }
+}
+
+/// Idle agents pick a nearby agent they do not dislike and both stop to talk, the initiator speaks first.
+#[allow(clippy::type_complexity)]
+fn strike_up_conversation_system(
+    mut commands: Commands,
+    mut query: Query<
+        (Entity, &ID, &Transform, &Personality, &Opinions, &mut Body),
+        (
+            With<Brain>,
+            Without<BehaviorTree>,
+            Without<Conversation>,
+            Without<Attending>,
+        ),
+    >,
+) {
+    let mut rng = rand::thread_rng();
+    let available: Vec<(Entity, String, Vec3)> = query
+        .iter()
+        .map(|(entity, id, transform, ..)| (entity, id.0.clone(), transform.translation))
+        .collect();
+    let mut engaged: Vec<Entity> = Vec::new();
+
+    for (entity, id, transform, personality, opinions, _) in query.iter() {
+        if engaged.contains(&entity) || !rng.gen_bool(personality.sociability()) {
+            continue;
+        }
+
+        let partner = available
+            .iter()
+            .filter(|(other, other_id, _)| {
+                *other != entity && !engaged.contains(other) && *other_id != id.0
+            })
+            .filter(|(_, other_id, _)| {
+                opinions
+                    .people
+                    .get(other_id)
+                    .is_none_or(|opinion| opinion.likeability >= 0.0)
+            })
+            .map(|(other, other_id, other_position)| {
+                (
+                    *other,
+                    other_id,
+                    transform.translation.distance(*other_position),
+                )
+            })
+            .filter(|(_, _, distance)| *distance < INTERLOCUTOR_RADIUS)
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+
+        if let Some((partner, partner_id, _)) = partner {
+            let turns_left = rng.gen_range(2..=6);
+            commands.entity(entity).insert(Conversation {
+                partner,
+                partner_id: partner_id.clone(),
+                speaking: true,
+                turns_left,
+            });
+            commands.entity(partner).insert(Conversation {
+                partner: entity,
+                partner_id: id.0.clone(),
+                speaking: false,
+                turns_left,
+            });
+            engaged.push(entity);
+            engaged.push(partner);
+        }
+    }
+
+    for (entity, _, transform, _, _, mut body) in query.iter_mut() {
+        if engaged.contains(&entity) {
+            body.target = Some(transform.translation);
+        }
+    }
+}
+
+/// Agents in a conversation take turns speaking to each other until they run out of turns and part.
+/// Badmouthing someone is whispered, anything else is said out loud and can be overheard.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn conversation_system(
+    mut commands: Commands,
+    mut spoken_events: EventWriter<SpokenEvent>,
+    sprites: Res<SpriteRegistry>,
+    topic_directory: Res<TopicDirectory>,
+    clock: Res<SimulationClock>,
+    args: Res<Args>,
+    mut query: Query<(
+        Entity,
+        &ID,
+        &Identity,
+        &Transform,
+        &Personality,
+        &Emotion,
+        &mut Opinions,
+        &mut Body,
+        &mut Conversation,
+    )>,
+) {
+    let speakers: Vec<(Entity, Entity)> = query
+        .iter()
+        .filter(|(.., conversation)| conversation.speaking)
+        .map(|(entity, .., conversation)| (entity, conversation.partner))
+        .collect();
+
+    for (speaker, partner) in speakers {
+        let partner_present = query
+            .get(partner)
+            .is_ok_and(|(.., conversation)| conversation.partner == speaker);
+        let (
+            _,
+            id,
+            identity,
+            transform,
+            personality,
+            emotion,
+            mut opinions,
+            mut body,
+            mut conversation,
+        ) = query.get_mut(speaker).unwrap();
+
+        if !partner_present || conversation.turns_left == 0 {
+            commands.entity(speaker).remove::<Conversation>();
+            body.target = None;
+            if partner_present {
+                commands.entity(partner).remove::<Conversation>();
+                let (.., mut partner_body, _) = query.get_mut(partner).unwrap();
+                partner_body.target = None;
+            }
+            continue;
+        }
+
+        let listeners = [conversation.partner_id.clone()];
+        let event = opinions
+            .generate_speakable_personal_opinion(
+                transform,
+                id,
+                identity,
+                clock.elapsed,
+                &listeners,
+                args.topic_bias,
+            )
+            .retold(personality, &opinions);
+        let badmouthing = event.opinion.as_ref().is_some_and(|(subject, opinion)| {
+            matches!(subject, Subject::Person(_)) && opinion.likeability < 0.0
+        });
+        let register = if badmouthing {
+            Register::Whisper
+        } else {
+            Register::Say
+        };
+        spawn_speech_bubble(
+            &mut commands,
+            &sprites,
+            speaker,
+            event.bubble(&topic_directory, emotion.mood()),
+        );
+        spoken_events.send(event.in_register(register));
+
+        conversation.speaking = false;
+        conversation.turns_left -= 1;
+
+        let (.., mut partner_conversation) = query.get_mut(partner).unwrap();
+        partner_conversation.speaking = true;
+        partner_conversation.turns_left = partner_conversation.turns_left.saturating_sub(1);
+    }
+}
+
+/// Agents walk up to whoever they caught badmouthing them and tell them what they think of them,
+/// giving up once the grudge has been nursed for long enough.
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+fn confrontation_system(
+    mut commands: Commands,
+    mut spoken_events: EventWriter<SpokenEvent>,
+    sprites: Res<SpriteRegistry>,
+    topic_directory: Res<TopicDirectory>,
+    transform_state: Res<TransformState>,
+    clock: Res<SimulationClock>,
+    mut query: Query<
+        (
+            Entity,
+            &ID,
+            &Identity,
+            &Transform,
+            &Emotion,
+            &Opinions,
+            &mut Body,
+            &Confronting,
+        ),
+        (Without<Conversation>, Without<Attending>),
+    >,
+) {
+    for (entity, id, identity, transform, emotion, opinions, mut body, confronting) in
+        query.iter_mut()
+    {
+        let speaker_position = transform_state
+            .transforms
+            .get(&confronting.speaker_id)
+            .map(|transform| transform.translation);
+
+        let speaker_position = match speaker_position {
+            Some(position) if clock.elapsed - confronting.since < GRUDGE_PATIENCE => position,
+            _ => {
+                commands.entity(entity).remove::<Confronting>();
+                body.target = None;
+                continue;
+            }
+        };
+
+        if transform.translation.distance(speaker_position) > CONVERSATION_RADIUS {
+            body.target = Some(speaker_position);
+            continue;
+        }
+
+        if let Some(event) = opinions.confront(&confronting.speaker_id, transform, id, identity) {
+            spawn_speech_bubble(
+                &mut commands,
+                &sprites,
+                entity,
+                event.bubble(&topic_directory, emotion.mood()),
+            );
+            spoken_events.send(event);
+        }
+        commands.entity(entity).remove::<Confronting>();
+        body.target = None;
+    }
+}
+
+/// Agents holding a grudge badmouth its target to the first audience that does not include the target.
+#[allow(clippy::type_complexity)]
+fn retaliation_system(
+    mut commands: Commands,
+    mut spoken_events: EventWriter<SpokenEvent>,
+    sprites: Res<SpriteRegistry>,
+    topic_directory: Res<TopicDirectory>,
+    transform_state: Res<TransformState>,
+    clock: Res<SimulationClock>,
+    query: Query<(
+        Entity,
+        &ID,
+        &Identity,
+        &Transform,
+        &Personality,
+        &Emotion,
+        &Opinions,
+        &Retaliating,
+    )>,
+) {
+    for (entity, id, identity, transform, personality, emotion, opinions, retaliating) in
+        query.iter()
+    {
+        if clock.elapsed - retaliating.since > GRUDGE_PATIENCE {
+            commands.entity(entity).remove::<Retaliating>();
+            continue;
+        }
+
+        let audience = audience(id, transform.translation, Register::Say, &transform_state);
+        if audience.is_empty() || audience.contains(&retaliating.speaker_id) {
+            continue;
+        }
+
+        if let Some(event) =
+            opinions.speak_about(&retaliating.speaker_id, transform, id, identity, &audience)
+        {
+            let event = event.retold(personality, opinions);
+            spawn_speech_bubble(
+                &mut commands,
+                &sprites,
+                entity,
+                event.bubble(&topic_directory, emotion.mood()),
+            );
+            spoken_events.send(event);
+        }
+        commands.entity(entity).remove::<Retaliating>();
+    }
+}
+
+/// Free agents join a nearby gathering when they get along with all of its members,
+/// otherwise agents who all like each other and stand close together form a new one.
+/// Agents who just left a gathering sit out until GATHERING_COOLDOWN has passed.
+#[allow(clippy::type_complexity)]
+fn gathering_formation_system(
+    mut commands: Commands,
+    clock: Res<SimulationClock>,
+    free: Query<
+        (Entity, &ID, &Transform, &Opinions, Option<&LeftGathering>),
+        (
+            With<Brain>,
+            Without<BehaviorTree>,
+            Without<Conversation>,
+            Without<Attending>,
+        ),
+    >,
+    attending: Query<&Opinions, With<Attending>>,
+    mut gatherings: Query<(Entity, &mut Gathering)>,
+) {
+    let rested = |left: Option<&LeftGathering>| {
+        left.is_none_or(|left| clock.elapsed - left.at >= GATHERING_COOLDOWN)
+    };
+    let mut engaged: Vec<Entity> = free
+        .iter()
+        .filter(|(.., left)| !rested(*left))
+        .map(|(entity, ..)| entity)
+        .collect();
+
+    for (entity, _, transform, opinions, left) in free.iter() {
+        if !rested(left) {
+            continue;
+        }
+
+        let joined = gatherings.iter_mut().find(|(_, gathering)| {
+            gathering.members.len() < MAX_GATHERING_SIZE
+                && gathering.center.distance(transform.translation) < GATHERING_RADIUS
+                && gathering.members.iter().all(|(member, _)| {
+                    attending
+                        .get(*member)
+                        .is_ok_and(|member_opinions| get_along(opinions, member_opinions))
+                })
+        });
+
+        if let Some((gathering_entity, mut gathering)) = joined {
+            gathering.members.push((entity, opinions.owner_id.clone()));
+            commands.entity(entity).insert(Attending {
+                gathering: gathering_entity,
+                boredom: 0.0,
+            });
+            engaged.push(entity);
+        }
+    }
+
+    for (anchor, _, anchor_transform, anchor_opinions, _) in free.iter() {
+        if engaged.contains(&anchor) {
+            continue;
+        }
+
+        let mut candidates: Vec<(Entity, Vec3, &Opinions)> = free
+            .iter()
+            .filter(|(other, ..)| *other != anchor && !engaged.contains(other))
+            .map(|(other, _, other_transform, other_opinions, _)| {
+                (other, other_transform.translation, other_opinions)
+            })
+            .filter(|(_, position, _)| {
+                position.distance(anchor_transform.translation) < GATHERING_RADIUS
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            let a_distance = a.1.distance(anchor_transform.translation);
+            let b_distance = b.1.distance(anchor_transform.translation);
+            a_distance.total_cmp(&b_distance)
+        });
+
+        let mut circle = vec![(anchor, anchor_transform.translation, anchor_opinions)];
+        for candidate in candidates {
+            if circle.len() == MAX_GATHERING_SIZE {
+                break;
+            }
+            if circle
+                .iter()
+                .all(|(_, _, member_opinions)| get_along(candidate.2, member_opinions))
+            {
+                circle.push(candidate);
+            }
+        }
+
+        if circle.len() < MIN_GATHERING_SIZE {
+            continue;
+        }
+
+        let center = circle
+            .iter()
+            .fold(Vec3::ZERO, |sum, (_, position, _)| sum + *position)
+            / circle.len() as f32;
+        let gathering = commands
+            .spawn()
+            .insert(Gathering {
+                center,
+                members: circle
+                    .iter()
+                    .map(|(member, _, member_opinions)| (*member, member_opinions.owner_id.clone()))
+                    .collect(),
+                speaker: 0,
+            })
+            .id();
+
+        for (member, ..) in circle {
+            commands.entity(member).insert(Attending {
+                gathering,
+                boredom: 0.0,
+            });
+            engaged.push(member);
+        }
+    }
+}
+
+/// Members of a gathering stand around its center and take turns speaking to the whole circle.
+/// They leave once bored or when someone they dislike is in the circle, the gathering breaks up below two members.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn gathering_system(
+    mut commands: Commands,
+    mut spoken_events: EventWriter<SpokenEvent>,
+    sprites: Res<SpriteRegistry>,
+    topic_directory: Res<TopicDirectory>,
+    clock: Res<SimulationClock>,
+    args: Res<Args>,
+    mut gatherings: Query<(Entity, &mut Gathering)>,
+    mut members: Query<(
+        &ID,
+        &Identity,
+        &Transform,
+        &Personality,
+        &Emotion,
+        &mut Opinions,
+        &mut Body,
+        &mut Attending,
+    )>,
+) {
+    for (gathering_entity, mut gathering) in gatherings.iter_mut() {
+        gathering.members.retain(|(member, _)| {
+            members
+                .get(*member)
+                .is_ok_and(|(.., attending)| attending.gathering == gathering_entity)
+        });
+
+        let member_ids: Vec<String> = gathering
+            .members
+            .iter()
+            .map(|(_, member_id)| member_id.clone())
+            .collect();
+        let mut leaving: Vec<Entity> = Vec::new();
+        for (member, _) in gathering.members.iter() {
+            let (_, _, _, personality, _, opinions, _, mut attending) =
+                members.get_mut(*member).unwrap();
+            attending.boredom += personality.boredom_rate();
+
+            let rival_present = member_ids.iter().any(|member_id| {
+                opinions
+                    .people
+                    .get(member_id)
+                    .is_some_and(|opinion| opinion.likeability < AVOIDANCE_LIKEABILITY)
+            });
+            if attending.boredom >= 1.0 || rival_present {
+                leaving.push(*member);
+            }
+        }
+
+        if gathering.members.len() - leaving.len() < 2 {
+            leaving = gathering
+                .members
+                .iter()
+                .map(|(member, _)| *member)
+                .collect();
+            commands.entity(gathering_entity).despawn();
+        }
+
+        for member in leaving.iter() {
+            commands
+                .entity(*member)
+                .remove::<Attending>()
+                .insert(LeftGathering { at: clock.elapsed });
+            members.get_mut(*member).unwrap().6.target = None;
+        }
+        gathering
+            .members
+            .retain(|(member, _)| !leaving.contains(member));
+
+        if gathering.members.is_empty() {
+            continue;
+        }
+
+        let seats = gathering.members.len();
+        for (seat, (member, _)) in gathering.members.iter().enumerate() {
+            members.get_mut(*member).unwrap().6.target =
+                Some(circle_seat(gathering.center, seat, seats));
+        }
+
+        gathering.speaker %= seats;
+        let (speaker, speaker_id) = gathering.members[gathering.speaker].clone();
+        let audience: Vec<String> = gathering
+            .members
+            .iter()
+            .map(|(_, member_id)| member_id.clone())
+            .filter(|member_id| *member_id != speaker_id)
+            .collect();
+        let (id, identity, transform, personality, emotion, mut opinions, ..) =
+            members.get_mut(speaker).unwrap();
+
+        let event = opinions
+            .generate_speakable_personal_opinion(
+                transform,
+                id,
+                identity,
+                clock.elapsed,
+                &audience,
+                args.topic_bias,
+            )
+            .retold(personality, &opinions);
+        spawn_speech_bubble(
+            &mut commands,
+            &sprites,
+            speaker,
+            event.bubble(&topic_directory, emotion.mood()),
+        );
+        spoken_events.send(event);
+
+        gathering.speaker += 1;
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn thought_system(
+    mut commands: Commands,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut lines: ResMut<DebugLines>,
+    mut spoken_events: EventReader<SpokenEvent>,
+    mut query: Query<
+        (
+            Entity,
+            &Transform,
+            &ID,
+            &Identity,
+            &Personality,
+            &Factions,
+            &mut Opinions,
+            &mut Emotion,
+            Option<&BehaviorTree>,
+        ),
+        With<Brain>,
+    >,
+    sprites: Res<SpriteRegistry>,
+    face_directory: Res<FaceDirectory>,
+    faction_directory: Res<FactionDirectory>,
+    opinion_dynamics: Res<OpinionDynamics>,
+    topic_directory: Res<TopicDirectory>,
+    clock: Res<SimulationClock>,
+) {
+    for spoken_event in spoken_events.iter() {
+        for (
+            entity,
+            transform,
+            id,
+            identity,
+            personality,
+            factions,
+            mut opinions,
+            mut emotion,
+            behavior,
+        ) in query.iter_mut()
+        {
+            let hearing_boost = match behavior {
+                Some(tree) if tree.listening => LISTENING_HEARING_BOOST,
+                _ => 1.0,
+            };
+
+            if spoken_event.reaches(&id.0, transform.translation, hearing_boost) {
+                let texture = sprites.thought.clone();
+
+                let bubble = commands
+                    .spawn_bundle(SpriteBundle {
+                        texture: texture.clone(),
+                        transform: Transform {
+                            translation: Vec3::new(40.0, 45.0, 2.0),
+                            scale: Vec3::new(1.0, 1.0, 1.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .insert(Lifetime(Timer::from_seconds(2.0, true)))
+                    .id();
+
+                if let Some((subject, personal_opinion)) = &spoken_event.opinion {
+                    let transmitted_opinion: PersonalOpinion = personal_opinion.clone();
+
+                    let listener = Listener {
+                        personality,
+                        appearance: &identity.0,
+                        face_directory: &face_directory,
+                        factions,
+                        faction_directory: &faction_directory,
+                    };
+
+                    if let Subject::Person(subject_id) = subject {
+                        if let Some(impact) =
+                            emotional_impact(&opinions, subject_id, &transmitted_opinion)
+                        {
+                            emotion.feel(impact, personality);
+                        }
+                    }
+
+                    match subject {
+                        Subject::Person(subject_id) if *subject_id == id.0 => {
+                            let retort = process_gossip_about_self(
+                                &mut opinions,
+                                &listener,
+                                opinion_dynamics.model.as_ref(),
+                                &spoken_event.author,
+                                &transmitted_opinion,
+                                spoken_event.confrontation,
+                                clock.elapsed,
+                            );
+                            let speaker_id = spoken_event.author.clone();
+                            match retort {
+                                Some(Retort::Confront) => {
+                                    debug!("{} confronts {}", id.0, speaker_id);
+                                    commands.entity(entity).insert(Confronting {
+                                        speaker_id,
+                                        since: clock.elapsed,
+                                    });
+                                }
+                                Some(Retort::Retaliate) => {
+                                    debug!("{} retaliates against {}", id.0, speaker_id);
+                                    commands.entity(entity).insert(Retaliating {
+                                        speaker_id,
+                                        since: clock.elapsed,
+                                    });
+                                }
+                                None => {}
+                            }
+                        }
+                        Subject::Person(subject_id) => process_heard_opinion(
+                            &mut opinions,
+                            &listener,
+                            opinion_dynamics.model.as_ref(),
+                            &spoken_event.author,
+                            subject_id,
+                            &transmitted_opinion,
+                            clock.elapsed,
+                        ),
+                        Subject::Topic(topic_id) => process_heard_stance(
+                            &mut opinions,
+                            &listener,
+                            opinion_dynamics.model.as_ref(),
+                            &spoken_event.author,
+                            topic_id,
+                            &transmitted_opinion,
+                            clock.elapsed,
+                        ),
+                    }
+
+                    if let Subject::Topic(topic_id) = subject {
+                        if let Some(topic) = topic_directory.get(topic_id) {
+                            let topic_icon = spawn_tinted_icon(
+                                &mut commands,
+                                &sprites,
+                                topic.emote(),
+                                topic.tint(),
+                                Vec3::new(5.0, 0.0, 4.0),
+                                0.6,
+                            );
+                            commands.entity(bubble).push_children(&[topic_icon]);
+                        }
+                    }
+
+                    let face_texture = match subject {
+                        Subject::Person(subject_id) => face_directory.faces.get(subject_id),
+                        Subject::Topic(_) => None,
+                    };
+
+                    if let Some(handle) = face_texture {
+                        let face_handle = handle;
+                        let face_atlas = TextureAtlas::from_grid(
+                            face_handle.clone(),
+                            Vec2::new(45.0, 45.0),
+                            1,
+                            1,
+                        );
+                        let face_atlas_handle = texture_atlases.add(face_atlas);
+                        let gossip_subject_face = commands
+                            .spawn_bundle(SpriteSheetBundle {
+                                texture_atlas: face_atlas_handle,
+                                transform: Transform {
+                                    translation: Vec3::new(5.0, 0.0, 4.0),
+                                    scale: Vec3::new(0.7, 0.7, 1.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .id();
+                        commands
+                            .entity(bubble)
+                            .push_children(&[gossip_subject_face]);
+                    }
+
+                    let mut start_color = Color::BLUE;
+                    let mut end_color = Color::GREEN;
+                    let mut value_icon_texture = sprites.thumbs_down.clone();
+
+                    if transmitted_opinion.likeability > 0.0 {
+                        start_color = Color::RED;
+                        end_color = Color::ORANGE;
+                        value_icon_texture = sprites.thumbs_up.clone();
+                    }
+
+                    let judgement_position = Vec3::new(-15.0, 3.0, 2.0);
+                    let judgement = match Icon::judgement(transmitted_opinion.likeability) {
+                        Some(icon) => {
+                            spawn_icon(&mut commands, &sprites, icon, judgement_position, 0.4)
+                        }
+                        None => commands
+                            .spawn_bundle(SpriteBundle {
+                                texture: value_icon_texture,
+                                transform: Transform {
+                                    translation: judgement_position,
+                                    scale: Vec3::new(0.5, 0.5, 1.0),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .id(),
+                    };
+
+                    commands.entity(bubble).push_children(&[judgement]);
+
+                    lines.line_gradient(
+                        spoken_event.origin,
+                        transform.translation,
+                        0.3,
+                        start_color,
+                        end_color,
+                    );
+                }
+
+                // Rendering head of the source model
+                //asset_server Load funcs should be in setup only, as they access filesystem.
+                let sprite_handle = &spoken_event.identity;
+                let texture_atlas =
+                    TextureAtlas::from_grid(sprite_handle.clone(), Vec2::new(45.0, 45.0), 1, 1);
+                let texture_atlas_handle = texture_atlases.add(texture_atlas);
+                let head = commands
+                    .spawn_bundle(SpriteSheetBundle {
+                        texture_atlas: texture_atlas_handle,
+                        transform: Transform {
+                            translation: Vec3::new(20.0, 20.0, 4.0),
+                            scale: Vec3::new(0.7, 0.7, 1.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .id();
+
+                commands.entity(bubble).push_children(&[head]);
+
+                // agents that stopped to listen prick up their ears
+                if hearing_boost > 1.0 {
+                    let ear = spawn_icon(
+                        &mut commands,
+                        &sprites,
+                        Icon::Ear,
+                        Vec3::new(-20.0, 20.0, 4.0),
+                        0.4,
+                    );
+                    commands.entity(bubble).push_children(&[ear]);
+                }
+
+                commands.entity(entity).push_children(&[bubble]);
+            }
+        }
+    }
+}
+
+fn clock_system(time: Res<Time>, mut clock: ResMut<SimulationClock>) {
+    clock.delta = time.delta_seconds().min(MAX_FRAME_DELTA);
+    clock.elapsed += clock.delta as f64;
+}
+
+fn lifetime_despawn_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Lifetime)>,
+) {
+    for (entity, mut auto_remove) in query.iter_mut() {
+        if auto_remove.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+// ============ SUBSYSTEMS ============
+
+fn spawn_speech_bubble(
+    commands: &mut Commands,
+    sprites: &SpriteRegistry,
+    speaker: Entity,
+    bubble: SpeechBubble,
+) {
+    let content = spawn_tinted_icon(
+        commands,
+        sprites,
+        bubble.content,
+        bubble.tint,
+        Vec3::new(0.0, 3.0, 1.0),
+        0.5,
+    );
+    let child = commands
+        .spawn_bundle(SpriteBundle {
+            texture: sprites.speech.clone(),
+            transform: Transform {
+                translation: Vec3::new(40.0, 45.0, 1.0),
+                scale: Vec3::new(1.0, 1.0, 1.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(Lifetime(Timer::from_seconds(2.0, true)))
+        .id();
+
+    commands.entity(child).push_children(&[content]);
+    if let Some(mood) = bubble.mood {
+        let mood = spawn_icon(commands, sprites, mood, Vec3::new(-20.0, 15.0, 2.0), 0.3);
+        commands.entity(child).push_children(&[mood]);
+    }
+    commands.entity(speaker).push_children(&[child]);
+}
+
+/// Sprite of a single emote atlas cell, to be attached to a bubble.
+fn spawn_icon(
+    commands: &mut Commands,
+    sprites: &SpriteRegistry,
+    icon: Icon,
+    translation: Vec3,
+    scale: f32,
+) -> Entity {
+    spawn_tinted_icon(commands, sprites, icon, Color::WHITE, translation, scale)
+}
+
+fn spawn_tinted_icon(
+    commands: &mut Commands,
+    sprites: &SpriteRegistry,
+    icon: Icon,
+    color: Color,
+    translation: Vec3,
+    scale: f32,
+) -> Entity {
+    commands
+        .spawn_bundle(SpriteSheetBundle {
+            texture_atlas: sprites.emotes.clone(),
+            sprite: TextureAtlasSprite {
+                color,
+                index: icon.index(),
+                ..Default::default()
+            },
+            transform: Transform {
+                translation,
+                scale: Vec3::new(scale, scale, 1.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .id()
+}
+
+/// Everyone but the speaker within earshot of the given register.
+fn audience(
+    id: &ID,
+    position: Vec3,
+    register: Register,
+    transform_state: &TransformState,
+) -> Vec<String> {
+    transform_state
+        .transforms
+        .iter()
+        .filter(|(other_id, other_transform)| {
+            **other_id != id.0 && position.distance(other_transform.translation) < register.range()
+        })
+        .map(|(other_id, _)| other_id.clone())
+        .collect()
+}
+
+/// Mean absolute difference between the likeability agents attribute to others and their merit.
+/// Opinions of oneself and of agents missing from `merits` are left out.
+fn mean_belief_error<'a>(
+    merits: &HashMap<String, f64>,
+    opinions: impl Iterator<Item = &'a Opinions>,
+) -> Option<f64> {
+    let (total, count) = opinions
+        .flat_map(|opinions| {
+            opinions
+                .people
+                .iter()
+                .filter(move |(person_id, _)| **person_id != opinions.owner_id)
+        })
+        .filter_map(|(person_id, opinion)| {
+            merits
+                .get(person_id)
+                .map(|merit| (opinion.likeability - merit).abs())
+        })
+        .fold((0.0, 0), |(total, count), error| (total + error, count + 1));
+
+    if count == 0 {
+        None
+    } else {
+        Some(total / count as f64)
+    }
+}
+
+/// Every agent's reputation, best first, see Reputation.
+fn reputation_ranking<'a>(agents: impl Iterator<Item = &'a Opinions>) -> Vec<(String, f64)> {
+    let agents: Vec<&Opinions> = agents.collect();
+    let others = (agents.len() as f64 - 1.0).max(1.0);
+
+    let mut ranking: Vec<(String, f64)> = agents
+        .iter()
+        .map(|agent| {
+            let esteem: f64 = agents
+                .iter()
+                .filter(|other| other.owner_id != agent.owner_id)
+                .filter_map(|other| other.people.get(&agent.owner_id))
+                .map(|opinion| opinion.likeability)
+                .sum();
+            (agent.owner_id.clone(), esteem / others)
+        })
+        .collect();
+    ranking.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranking
+}
+
+/// How members of every faction feel about each other and about members of other factions.
+fn faction_statistics<'a>(
+    faction_directory: &FactionDirectory,
+    agents: impl Iterator<Item = (&'a Factions, &'a Opinions)>,
+) -> Vec<FactionStatistics> {
+    let agents: Vec<(&Factions, &Opinions)> = agents.collect();
+    let mean = |values: Vec<f64>| {
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.iter().sum::<f64>() / values.len() as f64)
+        }
+    };
+
+    faction_directory
+        .factions
+        .iter()
+        .map(|faction| {
+            let members: Vec<&Opinions> = agents
+                .iter()
+                .filter(|(factions, _)| factions.0.contains(&faction.id))
+                .map(|(_, opinions)| *opinions)
+                .collect();
+
+            let mut in_group = Vec::new();
+            let mut out_group = Vec::new();
+            for opinions in members.iter() {
+                for (person_id, opinion) in opinions.people.iter() {
+                    let their_factions = faction_directory.factions_of(person_id);
+                    if *person_id == opinions.owner_id || their_factions.is_empty() {
+                        continue;
+                    }
+
+                    if their_factions.contains(&faction.id) {
+                        in_group.push(opinion.likeability);
+                    } else {
+                        out_group.push(opinion.likeability);
+                    }
+                }
+            }
+
+            FactionStatistics {
+                faction_id: faction.id.clone(),
+                members: members.len(),
+                in_group: mean(in_group),
+                out_group: mean(out_group),
+            }
+        })
+        .collect()
+}
+
+/// How good or bad gossip about `subject_id` is for the listener to hear, in -1..1.
+/// News about oneself hits hardest, news about friends less so and news about anyone else leaves the listener cold.
+fn emotional_impact(
+    opinions: &Opinions,
+    subject_id: &String,
+    transmitted_opinion: &PersonalOpinion,
+) -> Option<f64> {
+    let news = transmitted_opinion.likeability / 100.0;
+    if *subject_id == opinions.owner_id {
+        return Some(news);
+    }
+
+    match opinions.people.get(subject_id) {
+        Some(opinion) if opinion.likeability > COHESION_LIKEABILITY => {
+            Some(FRIEND_EMPATHY * news * opinion.likeability / 100.0)
+        }
+        _ => None,
+    }
+}
+
+/// Both agents hold a positive opinion of each other.
+fn get_along(a: &Opinions, b: &Opinions) -> bool {
+    let likes = |from: &Opinions, to: &Opinions| {
+        from.people
+            .get(&to.owner_id)
+            .is_some_and(|opinion| opinion.likeability > 0.0)
+    };
+
+    likes(a, b) && likes(b, a)
+}
+
+/// Evenly spaced spot on a circle around a gathering's center.
+fn circle_seat(center: Vec3, seat: usize, seats: usize) -> Vec3 {
+    let angle = std::f32::consts::TAU * seat as f32 / seats.max(1) as f32;
+
+    center + Vec3::new(angle.cos(), angle.sin(), 0.0) * GATHERING_SEAT_RADIUS
+}
+
+/// Weighted random draw from a ranked list, the first item is `bias` times more likely than the last,
+/// with the weights falling off geometrically in between.
+fn ranked_choice<T>(ranked: &[T], bias: f64) -> Option<&T> {
+    let last = ranked.len().saturating_sub(1).max(1) as f64;
+    let bias = bias.max(1.0);
+
+    (0..ranked.len())
+        .collect::<Vec<usize>>()
+        .choose_weighted(&mut rand::thread_rng(), |rank| {
+            bias.powf(-(*rank as f64) / last)
+        })
+        .ok()
+        .map(|rank| &ranked[*rank])
+}
+
+/// Closest disliked agent within perception range along with its distance.
+fn nearest_rival(
+    id: &ID,
+    position: Vec3,
+    opinions: &Opinions,
+    transform_state: &TransformState,
+) -> Option<(String, f32)> {
+    transform_state
+        .transforms
+        .iter()
+        .filter(|(other_id, _)| **other_id != id.0)
+        .filter(|(other_id, _)| {
+            opinions
+                .people
+                .get(*other_id)
+                .is_some_and(|opinion| opinion.likeability < AVOIDANCE_LIKEABILITY)
+        })
+        .map(|(other_id, other_transform)| {
+            (
+                other_id.clone(),
+                position.distance(other_transform.translation),
+            )
+        })
+        .filter(|(_, distance)| *distance < PERCEPTION_RADIUS)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Looks for a cluster of at least two other agents standing close together within perception range.
+/// Returns the center of the cluster and how much the agent likes the people in it on average, in -1..1.
+fn nearby_conversation(
+    id: &ID,
+    position: Vec3,
+    opinions: &Opinions,
+    transform_state: &TransformState,
+) -> Option<(Vec3, f32)> {
+    let nearby: Vec<(&String, Vec3)> = transform_state
+        .transforms
+        .iter()
+        .filter(|(other_id, other_transform)| {
+            **other_id != id.0 && position.distance(other_transform.translation) < PERCEPTION_RADIUS
+        })
+        .map(|(other_id, other_transform)| (other_id, other_transform.translation))
+        .collect();
+
+    nearby
+        .iter()
+        .filter_map(|(_, anchor)| {
+            let members: Vec<&(&String, Vec3)> = nearby
+                .iter()
+                .filter(|(_, other)| anchor.distance(*other) < CONVERSATION_RADIUS)
+                .collect();
+
+            if members.len() < 2 {
+                return None;
+            }
+
+            let center = members
+                .iter()
+                .fold(Vec3::ZERO, |sum, (_, other)| sum + *other)
+                / members.len() as f32;
+            let warmth = members
+                .iter()
+                .map(|(member_id, _)| {
+                    opinions
+                        .people
+                        .get(*member_id)
+                        .map_or(0.0, |opinion| opinion.likeability / 100.0)
+                })
+                .sum::<f64>()
+                / members.len() as f64;
+
+            Some((center, warmth as f32))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// The parts of a listening agent, beyond its opinions, which shape how it takes in what it hears.
+pub struct Listener<'a> {
+    pub personality: &'a Personality,
+    pub appearance: &'a Handle<Image>,
+    pub face_directory: &'a FaceDirectory,
+    pub factions: &'a Factions,
+    pub faction_directory: &'a FactionDirectory,
+}
+
+impl Listener<'_> {
+    fn shares_appearance(&self, person_id: &String) -> bool {
+        self.face_directory.faces.get(person_id) == Some(self.appearance)
+    }
+
+    /// Cohesion of the strongest faction shared with the person, or -0.5 when both belong to factions but none in common.
+    fn faction_affinity(&self, person_id: &String) -> f64 {
+        let their_factions = self.faction_directory.factions_of(person_id);
+        if self.factions.0.is_empty() || their_factions.is_empty() {
+            return 0.0;
+        }
+
+        self.factions
+            .0
+            .iter()
+            .filter(|faction_id| their_factions.contains(faction_id))
+            .filter_map(|faction_id| self.faction_directory.get(faction_id))
+            .map(|faction| faction.kind.cohesion())
+            .max_by(|a, b| a.total_cmp(b))
+            .unwrap_or(-0.5)
+    }
+
+    /// Multiplier on how much gossip from the speaker counts, fellow members are listened to more.
+    fn gossip_weight(&self, speaker_id: &String) -> f64 {
+        1.0 + FACTION_GOSSIP_WEIGHT * self.faction_affinity(speaker_id)
+    }
+
+    /// Agreeable listeners start out on a friendlier foot, and everyone favors people who look like themselves
+    /// or share a faction with them, while members of rival factions are met with suspicion.
+    /// Confident agents are more generous towards strangers, insecure ones more wary.
+    fn first_impression(&self, self_esteem: f64, person_id: &String) -> PersonalOpinion {
+        let mut rng = rand::thread_rng();
+        let mut bias = self.personality.impression_bias() + self_esteem / 10.0;
+
+        if self.shares_appearance(person_id) {
+            bias += self.personality.in_group_bias();
+        }
+        bias += FACTION_BIAS * self.faction_affinity(person_id);
+
+        let value: f64 = bias + rng.gen_range(-50.0..50.0);
+
+        PersonalOpinion::new(value, value)
+    }
+}
+
+/// Heard gossip is folded into the listener's opinion of the subject by the configured opinion model.
+/// Before that, the claim is checked against what the listener already thinks of the subject,
+/// speakers whose claims match the listener's own view gain trust and contradicted ones lose it.
+fn process_heard_opinion(
+    listener_opinions: &mut Opinions,
+    listener: &Listener,
+    model: &dyn OpinionModel,
+    speaker_id: &String,
+    subject_id: &String,
+    transmitted_opinion: &PersonalOpinion,
+    now: f64,
+) {
+    let mut rng = rand::thread_rng();
+    let self_esteem = listener_opinions.self_esteem();
+    let held_subject_opinion = listener_opinions.people.get(subject_id).cloned();
+
+    for person_id in [speaker_id, subject_id] {
+        if !listener_opinions.people.contains_key(person_id) {
+            let mut impression = listener.first_impression(self_esteem, person_id);
+            impression.reinforce(now);
+            listener_opinions.insert_opinion(person_id.clone(), impression);
+        }
+    }
+
+    // a claim at odds with the listener's own latest experience of the subject is taken for a lie and not listened to
+    let contradicts_experience = held_subject_opinion
+        .as_ref()
+        .and_then(|held| {
+            held.sources
+                .iter()
+                .rev()
+                .find(|testimony| testimony.speaker_id == listener_opinions.owner_id)
+        })
+        .is_some_and(|experience| {
+            (experience.value - transmitted_opinion.likeability).abs() >= CONTRADICTION_MARGIN
+        });
+    if contradicts_experience && rng.gen_bool(listener.personality.lie_detection()) {
+        listener_opinions
+            .people
+            .update(speaker_id, |speaker_opinion| {
+                speaker_opinion.adjust_trust(-LIE_PENALTY);
+                speaker_opinion.reinforce(now);
+            });
+        debug!(
+            "{} caught {} lying about {}",
+            listener_opinions.owner_id, speaker_id, subject_id
+        );
+        return;
+    }
+
+    if let Some(held_subject_opinion) = held_subject_opinion {
+        // 0 when the claim matches the listener's view exactly, 1 when it is the polar opposite
+        let discrepancy =
+            (transmitted_opinion.likeability - held_subject_opinion.likeability).abs() / 200.0;
+        listener_opinions
+            .people
+            .update(speaker_id, |speaker_opinion| {
+                speaker_opinion.adjust_trust(TRUST_LEARNING_RATE * (1.0 - 2.0 * discrepancy));
+                speaker_opinion.reinforce(now);
+            });
+
+        // sources at odds with each other, the less trusted one is taken to be wrong
+        let contradicted: Vec<String> = held_subject_opinion
+            .contradicting_sources(speaker_id, transmitted_opinion.likeability)
+            .map(|testimony| testimony.speaker_id.clone())
+            .collect();
+        for source_id in contradicted {
+            let speaker_trust = listener_opinions.people[speaker_id].trust;
+            let less_trusted_id = match listener_opinions.people.get(&source_id) {
+                // the listener's own experience always wins over hearsay
+                _ if source_id == listener_opinions.owner_id => speaker_id,
+                Some(source_opinion) if source_opinion.trust < speaker_trust => &source_id,
+                Some(_) => speaker_id,
+                None => continue,
+            };
+
+            let less_trusted_id = less_trusted_id.clone();
+            listener_opinions
+                .people
+                .update(&less_trusted_id, |less_trusted_opinion| {
+                    less_trusted_opinion.adjust_trust(-0.5 * TRUST_LEARNING_RATE);
+                });
+        }
+    }
+
+    // closed minded listeners let most gossip pass them by
+    if !rng.gen_bool(listener.personality.receptiveness()) {
+        return;
+    }
+
+    let claim = Claim {
+        value: transmitted_opinion.likeability,
+        speaker_trust: listener_opinions.people[speaker_id].trust,
+        susceptibility: listener.personality.susceptibility()
+            * listener.gossip_weight(speaker_id)
+            * listener_opinions.prestige(speaker_id),
+        firsthand: false,
+    };
+
+    let turned_against = listener_opinions
+        .people
+        .update(subject_id, |subject_opinion| {
+            let liked_before = subject_opinion.likeability >= 0.0;
+            model.update(subject_opinion, &claim);
+            subject_opinion.reinforce(now);
+            subject_opinion.record_testimony(Testimony {
+                speaker_id: speaker_id.clone(),
+                heard_at: now,
+                value: transmitted_opinion.likeability,
+            });
+            liked_before && subject_opinion.likeability < 0.0
+        })
+        .unwrap();
+
+    if turned_against {
+        debug!(
+            "{} turned against {} by {:?}",
+            listener_opinions.owner_id,
+            subject_id,
+            listener_opinions.turned_against_by(subject_id)
+        );
+    }
+}
+
+/// Gossip about the listener itself shows first hand what the speaker thinks of it,
+/// returns how the listener answers it.
+/// Being confronted is never answered with a confrontation, so two agents cannot keep telling each other off.
+fn process_gossip_about_self(
+    opinions: &mut Opinions,
+    listener: &Listener,
+    model: &dyn OpinionModel,
+    speaker_id: &String,
+    transmitted_opinion: &PersonalOpinion,
+    confronted: bool,
+    now: f64,
+) -> Option<Retort> {
+    process_encounter(
+        opinions,
+        listener,
+        model,
+        speaker_id,
+        transmitted_opinion.likeability,
+        now,
+    );
+
+    listener
+        .personality
+        .retort(transmitted_opinion.likeability)
+        .filter(|retort| !(confronted && *retort == Retort::Confront))
+}
+
+/// Folds the outcome of an interaction, in -100..100, into the agent's opinion of the person it met.
+/// The agent remembers it as its own testimony, so later gossip contradicting it is recognised as such.
+fn process_encounter(
+    opinions: &mut Opinions,
+    experiencer: &Listener,
+    model: &dyn OpinionModel,
+    other_id: &String,
+    outcome: f64,
+    now: f64,
+) {
+    if !opinions.people.contains_key(other_id) {
+        let self_esteem = opinions.self_esteem();
+        let impression = experiencer.first_impression(self_esteem, other_id);
+        opinions.insert_opinion(other_id.clone(), impression);
+    }
+
+    let claim = Claim {
+        value: outcome,
+        speaker_trust: 100.0,
+        susceptibility: experiencer.personality.susceptibility(),
+        firsthand: true,
+    };
+
+    let owner_id = opinions.owner_id.clone();
+    opinions.people.update(other_id, |opinion| {
+        model.update(opinion, &claim);
+        opinion.reinforce(now);
+        opinion.record_testimony(Testimony {
+            speaker_id: owner_id,
+            heard_at: now,
+            value: outcome,
+        });
+    });
+}
+
+/// Folds a claim about an abstract topic into the listener's stance on it,
+/// with the same trust weighting and receptiveness as gossip about people.
+fn process_heard_stance(
+    listener_opinions: &mut Opinions,
+    listener: &Listener,
+    model: &dyn OpinionModel,
+    speaker_id: &String,
+    topic_id: &str,
+    transmitted_stance: &PersonalOpinion,
+    now: f64,
+) {
+    if !listener_opinions.people.contains_key(speaker_id) {
+        let self_esteem = listener_opinions.self_esteem();
+        let mut impression = listener.first_impression(self_esteem, speaker_id);
+        impression.reinforce(now);
+        listener_opinions.insert_opinion(speaker_id.clone(), impression);
+    }
+
+    if !rand::thread_rng().gen_bool(listener.personality.receptiveness()) {
+        return;
+    }
+
+    let claim = Claim {
+        value: transmitted_stance.likeability,
+        speaker_trust: listener_opinions.people[speaker_id].trust,
+        susceptibility: listener.personality.susceptibility()
+            * listener.gossip_weight(speaker_id)
+            * listener_opinions.prestige(speaker_id),
+        firsthand: false,
+    };
+
+    let stance = listener_opinions
+        .stances
+        .entry(topic_id.to_string())
+        .or_insert_with(|| PersonalOpinion::new(0.0, 0.0));
+    model.update(stance, &claim);
+    stance.reinforce(now);
+    stance.record_testimony(Testimony {
+        speaker_id: speaker_id.clone(),
+        heard_at: now,
+        value: transmitted_stance.likeability,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn personality() -> Personality {
+        Personality {
+            openness: 1.0,
+            conscientiousness: 0.5,
+            extraversion: 0.5,
+            agreeableness: 0.5,
+            neuroticism: 0.5,
+        }
+    }
+
+    /// Runs `act` as a listener with the test personality, a face of its own and the given factions.
+    fn as_listener<R>(
+        factions: Factions,
+        faction_directory: FactionDirectory,
+        act: impl FnOnce(&Listener) -> R,
+    ) -> R {
+        let personality = personality();
+        let appearance = Handle::default();
+        let face_directory = FaceDirectory::default();
+
+        act(&Listener {
+            personality: &personality,
+            appearance: &appearance,
+            face_directory: &face_directory,
+            factions: &factions,
+            faction_directory: &faction_directory,
+        })
+    }
+
+    fn hear(
+        opinions: &mut Opinions,
+        speaker_id: &str,
+        subject_id: &str,
+        transmitted_opinion: &PersonalOpinion,
+    ) {
+        as_listener(
+            Factions::default(),
+            FactionDirectory::default(),
+            |listener| {
+                process_heard_opinion(
+                    opinions,
+                    listener,
+                    &opinion_model::TrustWeightedAccumulation,
+                    &speaker_id.to_string(),
+                    &subject_id.to_string(),
+                    transmitted_opinion,
+                    0.0,
+                )
+            },
+        );
+    }
+
+    fn opinions_with(people: &[(&str, f64, f64)]) -> Opinions {
+        let mut opinions = Opinions::new("listener".to_string(), &personality());
+        for (person_id, trust, likeability) in people {
+            opinions.insert_opinion(
+                person_id.to_string(),
+                PersonalOpinion::new(*trust, *likeability),
+            );
+        }
+
+        opinions
+    }
+
+    #[test]
+    fn steering_turns_the_velocity_into_a_full_speed_run() {
+        let mut body = Body::new(100.0, 1.0);
+        body.velocity = Vec3::new(0.0, 50.0, 0.0);
+
+        let change = body.steer_towards(Vec3::new(3.0, 0.0, 0.0));
+
+        assert_eq!(body.velocity + change, Vec3::new(100.0, 0.0, 0.0));
+        assert_eq!(body.steer_towards(Vec3::ZERO), Vec3::ZERO);
+    }
+
+    #[test]
+    fn arrival_slows_down_inside_the_arrival_radius() {
+        let mut body = Body::new(100.0, 1.0);
+
+        let far = body.arrive(Vec3::ZERO, Vec3::new(4.0 * ARRIVAL_RADIUS, 0.0, 0.0));
+        let near = body.arrive(Vec3::ZERO, Vec3::new(ARRIVAL_RADIUS / 2.0, 0.0, 0.0));
+        assert!((far.length() - 100.0).abs() < 1e-3);
+        assert!((near.length() - 50.0).abs() < 1e-3);
+
+        body.velocity = Vec3::new(30.0, 0.0, 0.0);
+        assert_eq!(body.arrive(Vec3::ZERO, Vec3::ZERO), -body.velocity);
+    }
+
+    #[test]
+    fn steering_force_is_capped_and_heavier_bodies_accelerate_slower() {
+        let mut light = Body::new(100.0, 1.0);
+        let mut heavy = Body::new(100.0, 2.0);
+
+        light.apply_force(Vec3::new(10.0 * MAX_STEERING_FORCE, 0.0, 0.0));
+        heavy.apply_force(Vec3::new(10.0 * MAX_STEERING_FORCE, 0.0, 0.0));
+
+        assert!((light.acceleration.length() - MAX_STEERING_FORCE).abs() < 1e-3);
+        assert!((heavy.acceleration.length() - MAX_STEERING_FORCE / 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn speed_is_capped_at_the_max_speed() {
+        let mut body = Body::new(100.0, 1.0);
+        let mut translation = Vec3::ZERO;
+        body.apply_force(Vec3::new(MAX_STEERING_FORCE, 0.0, 0.0));
+
+        body.integrate(&mut translation, 100.0, 1.0);
+
+        assert_eq!(body.velocity, Vec3::new(100.0, 0.0, 0.0));
+        assert_eq!(translation, Vec3::new(100.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn movement_does_not_depend_on_the_frame_rate() {
+        let simulate = |frames_per_second: u32| {
+            let mut body = Body::new(100.0, 1.0);
+            let mut translation = Vec3::ZERO;
+            for _ in 0..2 * frames_per_second {
+                let steering = body.arrive(translation, Vec3::new(1000.0, 0.0, 0.0));
+                body.apply_force(steering / STEERING_RESPONSE_TIME);
+                body.integrate(&mut translation, 100.0, 1.0 / frames_per_second as f32);
+            }
+            (translation, body.velocity)
+        };
+
+        let (slow_translation, slow_velocity) = simulate(20);
+        let (fast_translation, fast_velocity) = simulate(120);
+
+        assert!(slow_translation.distance(fast_translation) < 5.0);
+        assert!(slow_velocity.distance(fast_velocity) < 1.0);
+        assert!(fast_translation.x > 150.0);
+    }
+
+    #[test]
+    fn visits_move_pleasantness_towards_the_company_met() {
+        let mut location = LocationOpinion::default();
+
+        location.record_visit(&[("friend".to_string(), 100.0), ("rival".to_string(), 0.0)]);
+        location.record_visit(&[]);
+        location.record_visit(&[("friend".to_string(), 100.0)]);
+
+        assert_eq!(location.visits, 3);
+        let after_first = LOCATION_MEMORY_RATE * 50.0;
+        let after_third = after_first + LOCATION_MEMORY_RATE * (100.0 - after_first);
+        assert!((location.pleasantness - after_third).abs() < 1e-9);
+        assert_eq!(location.met.get("friend"), Some(&2));
+        assert_eq!(location.met.get("rival"), Some(&1));
+    }
+
+    #[test]
+    fn places_appeal_by_how_much_the_agent_likes_who_it_meets_there() {
+        let mut opinions = opinions_with(&[("friend", 50.0, 80.0), ("rival", 50.0, -40.0)]);
+        let mut market = LocationOpinion::default();
+        market.record_visit(&[("friend".to_string(), 80.0)]);
+        market.record_visit(&[("friend".to_string(), 80.0)]);
+        let mut tavern = LocationOpinion::default();
+        tavern.record_visit(&[("rival".to_string(), -40.0)]);
+        opinions
+            .locations
+            .insert("market".to_string(), market.clone());
+        opinions.locations.insert("tavern".to_string(), tavern);
+
+        let appeal = opinions.location_appeal(&"market".to_string()).unwrap();
+
+        let friend_likeability = opinions.people.get("friend").unwrap().likeability;
+        assert!((appeal - (market.pleasantness + friend_likeability)).abs() < 1e-9);
+        assert!(opinions.location_appeal(&"tavern".to_string()).unwrap() < 0.0);
+        assert_eq!(opinions.location_appeal(&"square".to_string()), None);
+        assert_eq!(opinions.get_fav_location_id(), Some("market".to_string()));
+    }
+
+    /// Lets an agent at the origin with the given temperament pick a goal, among people standing at the given spots.
+    fn deliberate(
+        extraversion: f32,
+        conscientiousness: f32,
+        opinions: &Opinions,
+        others: &[(&str, Vec3)],
+    ) -> Goal {
+        let personality = Personality {
+            extraversion,
+            conscientiousness,
+            ..personality()
+        };
+        let mut transform_state = TransformState::default();
+        for (person_id, position) in others {
+            transform_state.transforms.insert(
+                person_id.to_string(),
+                Transform::from_translation(*position),
+            );
+        }
+
+        Brain::default().deliberate(
+            &ID("listener".to_string()),
+            Vec3::ZERO,
+            &personality,
+            opinions,
+            &transform_state,
+            &LocationDirectory::default(),
+        )
+    }
+
+    #[test]
+    fn loners_rest_and_extraverts_wander_when_nobody_is_around() {
+        let opinions = opinions_with(&[]);
+
+        assert_eq!(deliberate(0.0, 0.0, &opinions, &[]), Goal::Rest);
+        assert_eq!(deliberate(1.0, 0.0, &opinions, &[]), Goal::Wander);
+    }
+
+    #[test]
+    fn extraverts_seek_out_a_liked_friend_in_sight() {
+        let opinions = opinions_with(&[("friend", 50.0, 1000.0)]);
+        let friend = [("friend", Vec3::new(400.0, 0.0, 0.0))];
+
+        assert_eq!(
+            deliberate(1.0, 0.5, &opinions, &friend),
+            Goal::SeekFriend("friend".to_string())
+        );
+    }
+
+    #[test]
+    fn a_hated_rival_close_by_is_avoided_but_a_distant_one_ignored() {
+        let opinions = opinions_with(&[("rival", 50.0, -1000.0)]);
+
+        assert_eq!(
+            deliberate(1.0, 0.5, &opinions, &[("rival", Vec3::new(10.0, 0.0, 0.0))]),
+            Goal::AvoidRival("rival".to_string())
+        );
+        assert_eq!(
+            deliberate(
+                1.0,
+                0.5,
+                &opinions,
+                &[("rival", Vec3::new(2.0 * PERCEPTION_RADIUS, 0.0, 0.0))]
+            ),
+            Goal::Wander
+        );
+    }
+
+    #[test]
+    fn sampled_traits_stay_within_bounds() {
+        let distribution = TraitDistribution {
+            mean: 0.9,
+            std_dev: 1.0,
+        };
+        let mut rng = rand::thread_rng();
+        let samples: Vec<f32> = (0..1000).map(|_| distribution.sample(&mut rng)).collect();
+
+        assert!(samples.iter().all(|value| (0.0..=1.0).contains(value)));
+        assert!(samples.contains(&0.0));
+        assert!(samples.contains(&1.0));
+    }
+
+    #[test]
+    fn shipped_personality_distribution_loads_with_defaults_for_missing_traits() {
+        let distribution =
+            PersonalityDistribution::load("assets/personalities/outgoing.ron").unwrap();
+
+        assert_eq!(distribution.extraversion.mean, 0.75);
+        assert_eq!(distribution.extraversion.std_dev, 0.1);
+        assert_eq!(distribution.conscientiousness.mean, 0.5);
+        assert_eq!(distribution.conscientiousness.std_dev, 0.15);
+        assert!(PersonalityDistribution::load("assets/personalities/missing.ron").is_err());
+    }
+
+    #[test]
+    fn calm_diligent_agents_think_more_of_themselves() {
+        let anxious = Personality {
+            conscientiousness: 0.0,
+            neuroticism: 1.0,
+            extraversion: 0.0,
+            ..personality()
+        };
+        let composed = Personality {
+            conscientiousness: 1.0,
+            neuroticism: 0.0,
+            extraversion: 1.0,
+            ..personality()
+        };
+
+        assert_eq!(anxious.self_regard(), (40.0, 0.0));
+        assert_eq!(composed.self_regard(), (160.0, 160.0));
+    }
+
+    /// Likeability seed of the first impression a listener of the given agreeableness forms of "stranger",
+    /// who looks like the listener when `same_sprite` is set.
+    fn first_impression(agreeableness: f32, same_sprite: bool) -> f64 {
+        let personality = Personality {
+            agreeableness,
+            ..personality()
+        };
+        let appearance = Handle::default();
+        let mut face_directory = FaceDirectory::default();
+        if same_sprite {
+            face_directory
+                .faces
+                .insert("stranger".to_string(), appearance.clone());
+        }
+
+        Listener {
+            personality: &personality,
+            appearance: &appearance,
+            face_directory: &face_directory,
+            factions: &Factions::default(),
+            faction_directory: &FactionDirectory::default(),
+        }
+        .first_impression(0.0, &"stranger".to_string())
+        .likeability_seed
+    }
+
+    #[test]
+    fn first_impressions_lean_on_agreeableness_and_looks() {
+        for _ in 0..100 {
+            assert!((-90.0..10.0).contains(&first_impression(0.0, false)));
+            assert!((-10.0..90.0).contains(&first_impression(1.0, false)));
+            let in_group = IN_GROUP_BIAS * (1.0 - personality().openness as f64 / 2.0);
+            assert!((in_group - 10.0..in_group + 90.0).contains(&first_impression(1.0, true)));
+        }
+    }
+
+    #[test]
+    fn outputs_are_logistic_squashed_seeds() {
+        let neutral = PersonalOpinion::new(0.0, 0.0);
+        assert_eq!(neutral.trust, 0.0);
+        assert_eq!(neutral.likeability, 0.0);
+
+        let fond = PersonalOpinion::new(100.0, -100.0);
+        assert!((fond.trust - 46.21).abs() < 0.01);
+        assert!((fond.likeability + 46.21).abs() < 0.01);
+
+        let extreme = PersonalOpinion::new(1e6, -1e6);
+        assert!(extreme.trust <= 100.0 && extreme.trust > 99.0);
+        assert!(extreme.likeability >= -100.0 && extreme.likeability < -99.0);
+    }
+
+    #[test]
+    fn adjustments_accumulate_into_the_seed() {
+        let mut opinion = PersonalOpinion::new(0.0, 0.0);
+
+        opinion.adjust_likeability(50.0);
+        let once = opinion.likeability;
+        opinion.adjust_likeability(50.0);
+
+        assert!(once > 0.0);
+        assert!(opinion.likeability > once);
+    }
+
+    #[test]
+    fn adjustments_are_tethered_at_half_strength() {
+        let mut liked = PersonalOpinion::new(0.0, 0.0);
+        liked.adjust_likeability(40.0);
+        assert!((liked.likeability_seed - 40.0).abs() < 1e-9);
+        assert!((liked.trust_seed - 20.0).abs() < 1e-9);
+
+        let mut trusted = PersonalOpinion::new(0.0, 0.0);
+        trusted.adjust_trust(40.0);
+        assert!((trusted.trust_seed - 40.0).abs() < 1e-9);
+        assert!((trusted.likeability_seed - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn accumulated_information_decays_towards_baseline() {
+        let mut opinion = PersonalOpinion::new(20.0, 20.0);
+        let baseline = opinion.likeability;
+
+        opinion.adjust_likeability(200.0);
+        assert!(opinion.likeability > baseline + 10.0);
+
+        for _ in 0..500 {
+            opinion.adjust_likeability(0.0);
+        }
+        assert!((opinion.likeability - baseline).abs() < 0.01);
+    }
+
+    #[test]
+    fn repeated_gossip_settles_instead_of_growing_without_bound() {
+        let mut opinion = PersonalOpinion::new(0.0, 0.0);
+
+        for _ in 0..1000 {
+            opinion.adjust_likeability(10.0);
+        }
+
+        let limit = 10.0 / OPINION_SEED_DECAY;
+        assert!((opinion.likeability_seed - limit).abs() < 0.01);
+    }
+
+    #[test]
+    fn trusted_speaker_shifts_opinion_towards_the_claim() {
+        let mut opinions = opinions_with(&[("speaker", 300.0, 100.0), ("subject", 0.0, 0.0)]);
+
+        hear(
+            &mut opinions,
+            "speaker",
+            "subject",
+            &PersonalOpinion::new(0.0, 100.0),
+        );
+
+        assert!(opinions.people["subject"].likeability > 0.0);
+    }
+
+    #[test]
+    fn distrusted_speaker_shifts_opinion_against_the_claim() {
+        let mut opinions = opinions_with(&[("speaker", -300.0, -100.0), ("subject", 0.0, 0.0)]);
+
+        hear(
+            &mut opinions,
+            "speaker",
+            "subject",
+            &PersonalOpinion::new(0.0, 100.0),
+        );
+
+        assert!(opinions.people["subject"].likeability < 0.0);
+    }
+
+    #[test]
+    fn contradicted_claims_lower_trust_in_the_speaker() {
+        let mut opinions = opinions_with(&[("speaker", 0.0, 0.0), ("subject", 0.0, 200.0)]);
+
+        hear(
+            &mut opinions,
+            "speaker",
+            "subject",
+            &PersonalOpinion::new(0.0, -200.0),
+        );
+
+        assert!(opinions.people["speaker"].trust < 0.0);
+    }
+
+    #[test]
+    fn corroborated_claims_raise_trust_in_the_speaker() {
+        let mut opinions = opinions_with(&[("speaker", 0.0, 0.0), ("subject", 0.0, 200.0)]);
+
+        hear(
+            &mut opinions,
+            "speaker",
+            "subject",
+            &PersonalOpinion::new(0.0, 200.0),
+        );
+
+        assert!(opinions.people["speaker"].trust > 0.0);
+    }
+
+    #[test]
+    fn ranking_stays_sorted_and_skips_the_owner() {
+        let opinions = opinions_with(&[
+            ("meh", 0.0, 10.0),
+            ("friend", 0.0, 200.0),
+            ("rival", 0.0, -200.0),
+            ("enemy", 0.0, -50.0),
+        ]);
+
+        let order: Vec<&str> = opinions
+            .people
+            .ranking()
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .collect();
+        assert_eq!(order, vec!["friend", "meh", "enemy", "rival"]);
+        assert_eq!(opinions.get_fav_person_id(), "friend");
+        assert_eq!(opinions.rivals().next().unwrap().0, "rival");
+    }
+
+    #[test]
+    fn favorite_is_dropped_once_disliked() {
+        let mut opinions = opinions_with(&[("friend", 0.0, 50.0), ("acquaintance", 0.0, 20.0)]);
+        assert_eq!(opinions.get_fav_person_id(), "friend");
+
+        opinions
+            .people
+            .update("friend", |friend| friend.set_likeability(-60.0));
+
+        assert_eq!(opinions.get_fav_person_id(), "acquaintance");
+        assert_eq!(opinions.rivals().next().unwrap().0, "friend");
+    }
+
+    #[test]
+    fn favorite_falls_back_to_self_when_nobody_is_liked() {
+        let opinions = opinions_with(&[("rival", 0.0, -100.0)]);
+
+        assert_eq!(opinions.get_fav_person_id(), "listener");
+        assert_eq!(opinions.favorites().count(), 0);
+    }
+
+    #[test]
+    fn opinions_fade_towards_neutral() {
+        let mut opinions = opinions_with(&[("friend", 100.0, 100.0), ("rival", -100.0, -100.0)]);
+        let self_esteem = opinions.self_esteem();
+
+        opinions.fade(OPINION_HALF_LIFE);
+
+        assert!((opinions.people["friend"].likeability_seed - 50.0).abs() < 1e-9);
+        assert!((opinions.people["rival"].trust_seed + 50.0).abs() < 1e-9);
+        assert_eq!(opinions.self_esteem(), self_esteem);
+        assert_eq!(
+            opinions.people.ranking()[0],
+            ("friend".to_string(), opinions.people["friend"].likeability)
+        );
+    }
+
+    #[test]
+    fn least_salient_people_are_forgotten_first() {
+        let mut opinions = opinions_with(&[
+            ("friend", 0.0, 200.0),
+            ("bystander", 0.0, 5.0),
+            ("rival", 0.0, -200.0),
+            ("stale", 0.0, 150.0),
+        ]);
+        for person_id in ["friend", "rival", "bystander"] {
+            opinions
+                .people
+                .update(person_id, |opinion| opinion.reinforce(1000.0));
+        }
+
+        opinions.forget_beyond(2, 1000.0);
+
+        assert_eq!(opinions.people.len(), 3);
+        assert!(opinions.people.contains_key("listener"));
+        assert!(opinions.people.contains_key("friend"));
+        assert!(opinions.people.contains_key("rival"));
+        assert_eq!(opinions.people.ranking().len(), 2);
+    }
+
+    #[test]
+    fn provenance_keeps_the_latest_sources() {
+        let mut opinions = opinions_with(&[("subject", 0.0, 0.0)]);
+        for i in 0..PROVENANCE_LENGTH + 2 {
+            hear(
+                &mut opinions,
+                &format!("speaker{}", i),
+                "subject",
+                &PersonalOpinion::new(0.0, 0.0),
+            );
+        }
+
+        let sources = &opinions.people["subject"].sources;
+        assert_eq!(sources.len(), PROVENANCE_LENGTH);
+        assert_eq!(sources.front().unwrap().speaker_id, "speaker2");
+        assert_eq!(
+            sources.back().unwrap().speaker_id,
+            format!("speaker{}", PROVENANCE_LENGTH + 1)
+        );
+    }
+
+    #[test]
+    fn remembers_who_turned_it_against_someone() {
+        let mut opinions = opinions_with(&[
+            ("subject", 0.0, 0.0),
+            ("gossip", 100.0, 0.0),
+            ("critic", 100.0, 0.0),
+            ("fan", 100.0, 0.0),
+        ]);
+
+        hear(
+            &mut opinions,
+            "critic",
+            "subject",
+            &PersonalOpinion::new(0.0, -50.0),
+        );
+        hear(
+            &mut opinions,
+            "gossip",
+            "subject",
+            &PersonalOpinion::new(0.0, -200.0),
+        );
+        hear(
+            &mut opinions,
+            "fan",
+            "subject",
+            &PersonalOpinion::new(0.0, 200.0),
+        );
+
+        let accusers: Vec<String> = opinions
+            .turned_against_by(&"subject".to_string())
+            .into_iter()
+            .map(|(speaker_id, _)| speaker_id)
+            .collect();
+        assert_eq!(accusers, vec!["gossip", "critic"]);
+    }
+
+    #[test]
+    fn contradicted_source_loses_trust() {
+        let mut opinions = opinions_with(&[
+            ("subject", 0.0, 0.0),
+            ("liar", 10.0, 0.0),
+            ("honest", 100.0, 0.0),
+        ]);
+        opinions.people.update("subject", |opinion| {
+            opinion.record_testimony(Testimony {
+                speaker_id: "liar".to_string(),
+                heard_at: 0.0,
+                value: 90.0,
+            })
+        });
+        let liar_trust = opinions.people["liar"].trust;
+
+        hear(
+            &mut opinions,
+            "honest",
+            "subject",
+            &PersonalOpinion::new(0.0, -200.0),
+        );
+
+        assert!(opinions.people["liar"].trust < liar_trust);
+    }
+
+    #[test]
+    fn strong_fresh_and_present_topics_rank_first() {
+        let mut opinions = opinions_with(&[
+            ("acquaintance", 0.0, 10.0),
+            ("old_flame", 0.0, 300.0),
+            ("rival", 0.0, -300.0),
+            ("bystander", 0.0, 10.0),
+        ]);
+        opinions
+            .people
+            .update("rival", |opinion| opinion.reinforce(1000.0));
+        opinions.people.remove("listener");
+        opinions
+            .stances
+            .insert("cola".to_string(), PersonalOpinion::new(0.0, 200.0));
+
+        let topics = opinions.ranked_topics(1000.0, &["bystander".to_string()]);
+
+        assert_eq!(
+            topics,
+            vec![
+                Subject::Person("rival".to_string()),
+                Subject::Person("old_flame".to_string()),
+                Subject::Topic("cola".to_string()),
+                Subject::Person("bystander".to_string()),
+                Subject::Person("acquaintance".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ranked_choice_favours_the_top_by_the_bias() {
+        let ranked = ["first", "middle", "last"];
+        let mut counts = [0; 3];
+        for _ in 0..6000 {
+            let choice = ranked_choice(&ranked, 4.0).unwrap();
+            counts[ranked.iter().position(|item| item == choice).unwrap()] += 1;
+        }
+
+        assert!(counts[0] > counts[1] && counts[1] > counts[2]);
+        assert!(counts[0] > 3 * counts[2]);
+        assert!(ranked_choice::<&str>(&[], 4.0).is_none());
+    }
+
+    fn spoken(register: Register, listeners: &[&str]) -> SpokenEvent {
+        SpokenEvent {
+            author: "speaker".to_string(),
+            origin: Vec3::ZERO,
+            distance: SPEECH_RADIUS,
+            register: Register::Say,
+            listeners: listeners.iter().map(|id| id.to_string()).collect(),
+            identity: Handle::default(),
+            opinion: None,
+            confrontation: false,
+        }
+        .in_register(register)
+    }
+
+    #[test]
+    fn whispers_only_reach_the_addressed() {
+        let whisper = spoken(Register::Whisper, &["partner"]);
+        let near = Vec3::new(10.0, 0.0, 0.0);
+
+        assert!(whisper.reaches(&"partner".to_string(), near, 1.0));
+        assert!(!whisper.reaches(&"eavesdropper".to_string(), near, LISTENING_HEARING_BOOST));
+        assert!(!whisper.reaches(&"speaker".to_string(), near, 1.0));
+    }
+
+    #[test]
+    fn announcements_carry_further_than_speech() {
+        let far = Vec3::new(SPEECH_RADIUS + 50.0, 0.0, 0.0);
+        let bystander = "bystander".to_string();
+
+        assert!(!spoken(Register::Say, &[]).reaches(&bystander, far, 1.0));
+        assert!(spoken(Register::Say, &[]).reaches(&bystander, far, LISTENING_HEARING_BOOST));
+        assert!(spoken(Register::Announce, &[]).reaches(&bystander, far, 1.0));
+    }
+
+    #[test]
+    fn only_mutual_liking_gets_along() {
+        let mut alice = Opinions::new("alice".to_string(), &personality());
+        let mut bob = Opinions::new("bob".to_string(), &personality());
+        alice.insert_opinion("bob".to_string(), PersonalOpinion::new(0.0, 50.0));
+        bob.insert_opinion("alice".to_string(), PersonalOpinion::new(0.0, -50.0));
+        assert!(!get_along(&alice, &bob));
+
+        bob.insert_opinion("alice".to_string(), PersonalOpinion::new(0.0, 50.0));
+        assert!(get_along(&alice, &bob));
+        assert!(get_along(&bob, &alice));
+    }
+
+    #[test]
+    fn gathering_seats_are_spread_around_the_center() {
+        let center = Vec3::new(100.0, 50.0, 0.0);
+        let seats: Vec<Vec3> = (0..4).map(|seat| circle_seat(center, seat, 4)).collect();
+
+        for seat in seats.iter() {
+            assert!((seat.distance(center) - GATHERING_SEAT_RADIUS).abs() < 1e-3);
+        }
+        assert!((seats[0].distance(seats[2]) - 2.0 * GATHERING_SEAT_RADIUS).abs() < 1e-3);
+    }
+
+    #[test]
+    fn stances_spread_from_trusted_speakers() {
+        let mut opinions = opinions_with(&[("friend", 100.0, 50.0)]);
+        as_listener(
+            Factions::default(),
+            FactionDirectory::default(),
+            |listener| {
+                for _ in 0..5 {
+                    process_heard_stance(
+                        &mut opinions,
+                        listener,
+                        &opinion_model::TrustWeightedAccumulation,
+                        &"friend".to_string(),
+                        "veganism",
+                        &PersonalOpinion::new(0.0, 200.0),
+                        0.0,
+                    );
+                }
+            },
+        );
+
+        let stance = &opinions.stances["veganism"];
+        assert!(stance.likeability > 0.0);
+        assert_eq!(stance.sources.len(), 5);
+        assert!(!opinions.people.contains_key("veganism"));
+    }
+
+    fn spoken_about(subject_id: &str, likeability_seed: f64) -> SpokenEvent {
+        let mut event = spoken(Register::Say, &[]);
+        event.opinion = Some((
+            Subject::Person(subject_id.to_string()),
+            PersonalOpinion::new(0.0, likeability_seed),
+        ));
+        event
+    }
+
+    fn retold_likeability(event: &SpokenEvent) -> f64 {
+        event.opinion.as_ref().unwrap().1.likeability
+    }
+
+    #[test]
+    fn retelling_exaggerates_or_flips_the_opinion() {
+        let opinions = opinions_with(&[]);
+        let original = retold_likeability(&spoken_about("subject", 50.0));
+
+        let mut exaggerated = spoken_about("subject", 50.0);
+        exaggerated.distort(Distortion::Exaggeration, &opinions);
+        assert!((retold_likeability(&exaggerated) - original * EXAGGERATION).abs() < 1e-6);
+
+        let mut flipped = spoken_about("subject", 50.0);
+        flipped.distort(Distortion::SignFlip, &opinions);
+        assert!((retold_likeability(&flipped) + original).abs() < 1e-6);
+    }
+
+    #[test]
+    fn misattribution_pins_the_opinion_on_someone_else() {
+        let mut opinions = opinions_with(&[("subject", 0.0, 0.0), ("scapegoat", 0.0, 0.0)]);
+        opinions.people.remove("listener");
+        let mut event = spoken_about("subject", 50.0);
+
+        event.distort(Distortion::Misattribution, &opinions);
+
+        assert_eq!(
+            event.opinion.unwrap().0,
+            Subject::Person("scapegoat".to_string())
+        );
+    }
+
+    #[test]
+    fn belief_error_measures_distance_from_merit() {
+        let mut merits = HashMap::new();
+        merits.insert("saint".to_string(), 80.0);
+        merits.insert("listener".to_string(), -100.0);
+        let mut opinions = opinions_with(&[("saint", 0.0, 0.0), ("unknown", 0.0, 0.0)]);
+        opinions
+            .people
+            .update("saint", |opinion| opinion.set_likeability(60.0));
+
+        let error = mean_belief_error(&merits, std::iter::once(&opinions)).unwrap();
+
+        assert!((error - 20.0).abs() < 0.01);
+        assert!(mean_belief_error(&HashMap::new(), std::iter::once(&opinions)).is_none());
+    }
+
+    #[test]
+    fn flattery_pays_in_front_of_the_subject_or_its_friends() {
+        let mut opinions = opinions_with(&[("subject", 0.0, -100.0), ("friend", 0.0, 0.0)]);
+        let mut event = spoken_about("subject", -100.0);
+        assert!(!event.flattery_pays(&opinions));
+
+        event.listeners = vec!["subject".to_string()];
+        assert!(event.flattery_pays(&opinions));
+
+        event.listeners = vec!["friend".to_string()];
+        opinions.people.update("subject", |opinion| {
+            opinion.record_testimony(Testimony {
+                speaker_id: "friend".to_string(),
+                heard_at: 0.0,
+                value: 60.0,
+            })
+        });
+        assert!(event.flattery_pays(&opinions));
+
+        let disliked = retold_likeability(&event);
+        event.lie();
+        assert!((retold_likeability(&event) + disliked).abs() < 1e-6);
+    }
+
+    #[test]
+    fn claims_against_firsthand_experience_are_caught_as_lies() {
+        let mut opinions = opinions_with(&[("subject", 0.0, 0.0), ("liar", 100.0, 0.0)]);
+        experience(&mut opinions, "subject", 90.0);
+        let subject_likeability = opinions.people["subject"].likeability;
+        let liar_trust = opinions.people["liar"].trust;
+
+        hear(
+            &mut opinions,
+            "liar",
+            "subject",
+            &PersonalOpinion::new(0.0, -200.0),
+        );
+
+        assert!(opinions.people["liar"].trust < liar_trust);
+        assert_eq!(opinions.people["subject"].likeability, subject_likeability);
+    }
+
+    #[test]
+    fn changing_ones_mind_is_not_taken_for_a_lie() {
+        let mut opinions = opinions_with(&[("subject", 0.0, 0.0), ("speaker", 100.0, 0.0)]);
+        hear(
+            &mut opinions,
+            "speaker",
+            "subject",
+            &PersonalOpinion::new(0.0, -200.0),
+        );
+        let subject_likeability = opinions.people["subject"].likeability;
+
+        hear(
+            &mut opinions,
+            "speaker",
+            "subject",
+            &PersonalOpinion::new(0.0, 200.0),
+        );
+
+        assert!(opinions.people["subject"].likeability > subject_likeability);
+    }
+
+    fn experience(opinions: &mut Opinions, other_id: &str, outcome: f64) {
+        as_listener(
+            Factions::default(),
+            FactionDirectory::default(),
+            |experiencer| {
+                process_encounter(
+                    opinions,
+                    experiencer,
+                    &opinion_model::TrustWeightedAccumulation,
+                    &other_id.to_string(),
+                    outcome,
+                    0.0,
+                )
+            },
+        );
+    }
+
+    #[test]
+    fn experience_outweighs_hearsay() {
+        let mut heard = opinions_with(&[("subject", 0.0, 0.0), ("friend", 1000.0, 0.0)]);
+        hear(
+            &mut heard,
+            "friend",
+            "subject",
+            &PersonalOpinion::new(0.0, 1000.0),
+        );
+
+        let mut met = opinions_with(&[("subject", 0.0, 0.0)]);
+        experience(
+            &mut met,
+            "subject",
+            heard.people["subject"].sources[0].value,
+        );
+
+        assert!(met.people["subject"].likeability > heard.people["subject"].likeability);
+        assert_eq!(met.people["subject"].sources[0].speaker_id, "listener");
+    }
+
+    #[test]
+    fn hearsay_contradicting_experience_costs_the_speaker_trust() {
+        let mut opinions = opinions_with(&[("subject", 0.0, 0.0), ("gossip", 0.0, 0.0)]);
+        experience(&mut opinions, "subject", 80.0);
+        let gossip_trust = opinions.people["gossip"].trust;
+        let self_trust = opinions.people["listener"].trust;
+
+        hear(
+            &mut opinions,
+            "gossip",
+            "subject",
+            &PersonalOpinion::new(0.0, -1000.0),
+        );
+
+        assert!(opinions.people["gossip"].trust < gossip_trust);
+        assert_eq!(opinions.people["listener"].trust, self_trust);
+    }
+
+    #[test]
+    fn kind_company_makes_for_pleasant_encounters() {
+        let mut kind = personality();
+        kind.agreeableness = 1.0;
+        let mut rude = personality();
+        rude.agreeableness = 0.0;
+
+        assert!(personality().rapport(&kind) > 0.0);
+        assert!(personality().rapport(&rude) < 0.0);
+    }
+
+    #[test]
+    fn strangers_get_a_first_impression() {
+        let mut opinions = opinions_with(&[]);
+
+        hear(
+            &mut opinions,
+            "speaker",
+            "subject",
+            &PersonalOpinion::new(0.0, 50.0),
+        );
+
+        assert!(opinions.people.contains_key("speaker"));
+        assert!(opinions.people.contains_key("subject"));
+    }
+
+    fn faction_directory() -> FactionDirectory {
+        let mut directory = FactionDirectory {
+            factions: vec![
+                FactionDefinition {
+                    id: "reds".to_string(),
+                    kind: FactionKind::Team,
+                    share: 0.5,
+                    color: (1.0, 0.0, 0.0),
+                },
+                FactionDefinition {
+                    id: "blues".to_string(),
+                    kind: FactionKind::Team,
+                    share: 0.5,
+                    color: (0.0, 0.0, 1.0),
+                },
+            ],
+            ..Default::default()
+        };
+        directory.join("listener", "reds");
+        directory.join("teammate", "reds");
+        directory.join("opponent", "blues");
+
+        directory
+    }
+
+    #[test]
+    fn fellow_faction_members_are_favored_and_listened_to() {
+        let reds = Factions(vec!["reds".to_string()]);
+
+        as_listener(reds, faction_directory(), |listener| {
+            assert!(listener.faction_affinity(&"teammate".to_string()) > 0.0);
+            assert!(listener.faction_affinity(&"opponent".to_string()) < 0.0);
+            assert_eq!(listener.faction_affinity(&"loner".to_string()), 0.0);
+            assert!(
+                listener.gossip_weight(&"teammate".to_string())
+                    > listener.gossip_weight(&"opponent".to_string())
+            );
+        });
+    }
+
+    #[test]
+    fn faction_statistics_split_in_group_from_out_group_feelings() {
+        let faction_directory = faction_directory();
+        let reds = Factions(vec!["reds".to_string()]);
+        let opinions = opinions_with(&[
+            ("teammate", 50.0, 80.0),
+            ("opponent", 50.0, -40.0),
+            ("loner", 50.0, 100.0),
+        ]);
+
+        let statistics = faction_statistics(&faction_directory, [(&reds, &opinions)].into_iter());
+
+        assert_eq!(statistics[0].faction_id, "reds");
+        assert_eq!(statistics[0].members, 1);
+        assert!(statistics[0].in_group.unwrap() > 0.0);
+        assert!(statistics[0].out_group.unwrap() < 0.0);
+        assert_eq!(statistics[1].members, 0);
+        assert_eq!(statistics[1].in_group, None);
+    }
+
+    #[test]
+    fn news_about_oneself_stirs_more_than_news_about_friends() {
+        let opinions = opinions_with(&[("friend", 50.0, 100.0), ("stranger", 50.0, 0.0)]);
+        let insult = PersonalOpinion::new(0.0, -1000.0);
+
+        let about_self = emotional_impact(&opinions, &"listener".to_string(), &insult).unwrap();
+        let about_friend = emotional_impact(&opinions, &"friend".to_string(), &insult).unwrap();
+
+        assert!(about_self < about_friend && about_friend < 0.0);
+        assert_eq!(
+            emotional_impact(&opinions, &"stranger".to_string(), &insult),
+            None
+        );
+    }
+
+    #[test]
+    fn anger_cools_into_unease_and_then_calm() {
+        let mut emotion = Emotion::default();
+        emotion.feel(-1.0, &personality());
+        assert_eq!(emotion.mood(), Mood::Angry);
+        assert!(emotion.speed() > 1.0);
+
+        emotion.settle(2.0 * AROUSAL_HALF_LIFE);
+        assert_eq!(emotion.mood(), Mood::Anxious);
+
+        emotion.settle(5.0 * VALENCE_HALF_LIFE);
+        assert_eq!(emotion.mood(), Mood::Calm);
+        assert_eq!(emotion.mood().emote(), None);
+    }
+
+    #[test]
+    fn praise_cheers_agents_up_and_loosens_their_tongues() {
+        let mut emotion = Emotion::default();
+        emotion.feel(0.8, &personality());
+
+        assert_eq!(emotion.mood(), Mood::Happy);
+        assert!(emotion.chattiness() > Emotion::default().chattiness());
+    }
+
+    #[test]
+    fn speech_bubbles_show_what_is_being_talked_about() {
+        let topic_directory = TopicDirectory {
+            topics: vec![TopicDefinition {
+                id: "cola".to_string(),
+                kind: TopicKind::Brand,
+                adoption: 0.5,
+                stance: 50.0,
+                icon: None,
+                color: untinted(),
+            }],
+        };
+        let mut about_cola = spoken(Register::Say, &[]);
+        about_cola.opinion = Some((
+            Subject::Topic("cola".to_string()),
+            PersonalOpinion::new(0.0, 50.0),
+        ));
+
+        assert_eq!(
+            spoken(Register::Say, &[]).icon(&topic_directory),
+            Icon::Question
+        );
+        assert_eq!(about_cola.icon(&topic_directory), Icon::Brand);
+        assert_eq!(
+            spoken_about("subject", 200.0).icon(&topic_directory),
+            Icon::Praise
+        );
+        assert_eq!(
+            spoken_about("subject", -200.0).icon(&topic_directory),
+            Icon::Insult
+        );
+    }
+
+    #[test]
+    fn speech_bubbles_honor_topic_overrides_and_show_the_mood() {
+        let topic_directory = TopicDirectory {
+            topics: vec![TopicDefinition {
+                id: "flat-earth".to_string(),
+                kind: TopicKind::Idea,
+                adoption: 0.5,
+                stance: 50.0,
+                icon: Some(Icon::Belief),
+                color: (0.9, 0.3, 0.3),
+            }],
+        };
+        let mut about_flat_earth = spoken(Register::Say, &[]);
+        about_flat_earth.opinion = Some((
+            Subject::Topic("flat-earth".to_string()),
+            PersonalOpinion::new(0.0, 50.0),
+        ));
+
+        assert_eq!(
+            about_flat_earth.bubble(&topic_directory, Mood::Angry),
+            SpeechBubble {
+                content: Icon::Belief,
+                tint: Color::rgb(0.9, 0.3, 0.3),
+                mood: Some(Icon::Angry),
+            }
+        );
+        assert_eq!(
+            spoken_about("subject", 200.0)
+                .bubble(&topic_directory, Mood::Calm)
+                .mood,
+            None
+        );
+    }
+
+    #[test]
+    fn newcomers_ask_around() {
+        let mut newcomer = opinions_with(&[]);
+        let id = ID("listener".to_string());
+        let identity = Identity(Handle::default());
+        let transform = Transform::default();
+
+        let question =
+            newcomer.generate_speakable_personal_opinion(&transform, &id, &identity, 0.0, &[], 2.0);
+        assert!(question.opinion.is_none());
+        assert_eq!(question.icon(&TopicDirectory::default()), Icon::Question);
+
+        newcomer.insert_opinion("friend".to_string(), PersonalOpinion::new(50.0, 80.0));
+        let gossip =
+            newcomer.generate_speakable_personal_opinion(&transform, &id, &identity, 0.0, &[], 2.0);
+        assert!(gossip.opinion.is_some());
+    }
+
+    #[test]
+    fn only_strong_judgements_get_their_own_icon() {
+        assert_eq!(Icon::judgement(90.0), Some(Icon::Praise));
+        assert_eq!(Icon::judgement(-90.0), Some(Icon::Insult));
+        assert_eq!(Icon::judgement(20.0), None);
+        assert_eq!(Icon::Belief.index(), EMOTE_COLUMNS - 1);
+    }
+
+    #[test]
+    fn overheard_badmouthing_sours_the_subject_on_the_speaker() {
+        let mut about_someone_else = opinions_with(&[("speaker", 50.0, 0.0)]);
+        let mut about_self = opinions_with(&[("speaker", 50.0, 0.0)]);
+        let insult = PersonalOpinion::new(0.0, -1000.0);
+
+        hear(&mut about_someone_else, "speaker", "subject", &insult);
+        as_listener(
+            Factions::default(),
+            FactionDirectory::default(),
+            |listener| {
+                process_gossip_about_self(
+                    &mut about_self,
+                    listener,
+                    &opinion_model::TrustWeightedAccumulation,
+                    &"speaker".to_string(),
+                    &insult,
+                    false,
+                    0.0,
+                )
+            },
+        );
+
+        assert!(about_self.people["speaker"].likeability < 0.0);
+        assert!(
+            about_self.people["speaker"].likeability
+                < about_someone_else.people["speaker"].likeability
+        );
+    }
+
+    #[test]
+    fn praise_never_provokes_a_retort() {
+        for _ in 0..100 {
+            assert_eq!(personality().retort(80.0), None);
+        }
+    }
+
+    #[test]
+    fn agents_only_speak_about_people_they_know() {
+        let opinions = opinions_with(&[("friend", 50.0, 80.0)]);
+        let id = ID("listener".to_string());
+        let identity = Identity(Handle::default());
+        let transform = Transform::default();
+
+        let event = opinions
+            .speak_about(&"friend".to_string(), &transform, &id, &identity, &[])
+            .unwrap();
+
+        assert_eq!(
+            event.opinion.unwrap().0,
+            Subject::Person("friend".to_string())
+        );
+        assert!(opinions
+            .speak_about(&"stranger".to_string(), &transform, &id, &identity, &[])
+            .is_none());
+    }
+
+    #[test]
+    fn confronted_speakers_hear_what_the_confronter_thinks_of_them() {
+        let confronter = opinions_with(&[("speaker", 50.0, -80.0)]);
+        let mut speaker = opinions_with(&[("listener", 50.0, 20.0)]);
+        let likeability = speaker.people["listener"].likeability;
+
+        let event = confronter
+            .confront(
+                &"speaker".to_string(),
+                &Transform::default(),
+                &ID("listener".to_string()),
+                &Identity(Handle::default()),
+            )
+            .unwrap();
+        assert_eq!(event.listeners, vec!["speaker".to_string()]);
+        assert!(event.confrontation);
+        let (subject, rebuttal) = event.opinion.unwrap();
+        assert_eq!(subject, Subject::Person("speaker".to_string()));
+
+        as_listener(
+            Factions::default(),
+            FactionDirectory::default(),
+            |listener| {
+                process_gossip_about_self(
+                    &mut speaker,
+                    listener,
+                    &opinion_model::TrustWeightedAccumulation,
+                    &event.author,
+                    &rebuttal,
+                    event.confrontation,
+                    0.0,
+                )
+            },
+        );
+
+        assert!(speaker.people["listener"].likeability < likeability);
+    }
+
+    #[test]
+    fn bold_agents_confront_gossip_but_not_being_confronted() {
+        let bold = Personality {
+            extraversion: 1.0,
+            agreeableness: 0.0,
+            neuroticism: 0.0,
+            ..personality()
+        };
+        let appearance = Handle::default();
+        let face_directory = FaceDirectory::default();
+        let listener = Listener {
+            personality: &bold,
+            appearance: &appearance,
+            face_directory: &face_directory,
+            factions: &Factions::default(),
+            faction_directory: &FactionDirectory::default(),
+        };
+        let insult = PersonalOpinion::new(0.0, -1000.0);
+        let retorts = |confronted: bool| -> Vec<Option<Retort>> {
+            (0..100)
+                .map(|_| {
+                    process_gossip_about_self(
+                        &mut opinions_with(&[("speaker", 50.0, 0.0)]),
+                        &listener,
+                        &opinion_model::TrustWeightedAccumulation,
+                        &"speaker".to_string(),
+                        &insult,
+                        confronted,
+                        0.0,
+                    )
+                })
+                .collect()
+        };
+
+        assert!(retorts(false).contains(&Some(Retort::Confront)));
+        assert!(!retorts(true).contains(&Some(Retort::Confront)));
+    }
+
+    #[test]
+    fn perceived_status_comes_from_what_others_say() {
+        let mut opinions = opinions_with(&[("speaker", 50.0, 0.0), ("fan", 50.0, 0.0)]);
+        assert_eq!(opinions.perceived_status(&"speaker".to_string()), None);
+
+        hear(
+            &mut opinions,
+            "speaker",
+            "speaker",
+            &PersonalOpinion::new(0.0, -1000.0),
+        );
+        assert_eq!(opinions.perceived_status(&"speaker".to_string()), None);
+
+        hear(
+            &mut opinions,
+            "fan",
+            "speaker",
+            &PersonalOpinion::new(0.0, 1000.0),
+        );
+        assert!(opinions.perceived_status(&"speaker".to_string()).unwrap() > 0.0);
+        assert!(opinions.prestige(&"speaker".to_string()) > 1.0);
+    }
+
+    #[test]
+    fn esteemed_speakers_are_more_persuasive() {
+        let people = [
+            ("speaker", 50.0, 0.0),
+            ("fan", 50.0, 0.0),
+            ("critic", 50.0, 0.0),
+            ("subject", 50.0, 0.0),
+        ];
+        let mut convinced = opinions_with(&people);
+        let mut doubtful = opinions_with(&people);
+
+        hear(
+            &mut convinced,
+            "fan",
+            "speaker",
+            &PersonalOpinion::new(0.0, 1000.0),
+        );
+        hear(
+            &mut doubtful,
+            "critic",
+            "speaker",
+            &PersonalOpinion::new(0.0, -1000.0),
+        );
+        for opinions in [&mut convinced, &mut doubtful] {
+            hear(
+                opinions,
+                "speaker",
+                "subject",
+                &PersonalOpinion::new(0.0, 1000.0),
+            );
+        }
+
+        assert!(convinced.people["subject"].likeability > doubtful.people["subject"].likeability);
+    }
+
+    #[test]
+    fn reputation_rewards_being_liked_by_many() {
+        let mut popular = opinions_with(&[]);
+        popular.owner_id = "popular".to_string();
+        let mut adored = opinions_with(&[("popular", 50.0, 60.0)]);
+        adored.owner_id = "adored".to_string();
+        let mut third = opinions_with(&[("popular", 50.0, 60.0), ("adored", 50.0, 100.0)]);
+        third.owner_id = "third".to_string();
+
+        let ranking = reputation_ranking([&popular, &adored, &third].into_iter());
+        let order: Vec<&str> = ranking.iter().map(|(id, _)| id.as_str()).collect();
 
-        PersonalOpinion::new(value.clone(), value.clone())
+        assert_eq!(order, ["popular", "adored", "third"]);
+        assert_eq!(ranking[2].1, 0.0);
     }
 }
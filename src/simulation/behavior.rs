@@ -0,0 +1,384 @@
+//! Data driven agent behaviors.
+//!
+//! A behavior tree is authored in a RON file and attached to agents through the `BehaviorTree` component,
+//! agents carrying one skip the built in executive functioning and chatter.
+//! Trees are re-evaluated from the root on every tick, there is no running state,
+//! so long lasting behaviors are expressed through the movement target left on the `Body`.
+
+use super::{
+    nearest_rival, LocationDirectory, Opinions, Personality, TransformState, ARRIVAL_RADIUS, ID,
+    WANDER_DISTANCE,
+};
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::Deserialize;
+use std::fs;
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum Node {
+    /// Succeeds once every child succeeds, stops at the first failure.
+    Sequence(Vec<Node>),
+    /// Succeeds at the first child that succeeds.
+    Selector(Vec<Node>),
+    Inverter(Box<Node>),
+    Condition(Condition),
+    Action(Action),
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum Trait {
+    Openness,
+    Conscientiousness,
+    Extraversion,
+    Agreeableness,
+    Neuroticism,
+}
+
+impl Trait {
+    /// Trait value on a 0..100 scale.
+    fn value(&self, personality: &Personality) -> f32 {
+        let value = match self {
+            Trait::Openness => personality.openness,
+            Trait::Conscientiousness => personality.conscientiousness,
+            Trait::Extraversion => personality.extraversion,
+            Trait::Agreeableness => personality.agreeableness,
+            Trait::Neuroticism => personality.neuroticism,
+        };
+
+        value * 100.0
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum Condition {
+    /// Succeeds with the given probability in 0..1.
+    Chance(f32),
+    TraitAbove(Trait, f32),
+    TraitBelow(Trait, f32),
+    /// Likeability of the favorite person is above the value, fails when the agent's favorite is itself.
+    FavoriteAbove(f64),
+    /// A disliked agent is closer than the given distance.
+    RivalWithin(f32),
+    /// The favorite person is closer than the given distance.
+    FavoriteWithin(f32),
+    /// Number of people the agent holds an opinion on, itself included.
+    KnowsAtLeast(usize),
+    InLocation(String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum Target {
+    Favorite,
+    FavoriteLocation,
+    Location(String),
+    /// A random point in walking distance, kept until reached.
+    Random,
+    Point(f32, f32),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum Action {
+    MoveTo(Target),
+    /// Walk away from the nearest rival, fails if there is none around.
+    Flee,
+    Speak,
+    /// Stand still and pay attention, hearing speech from further away.
+    Listen,
+    Idle,
+}
+
+/// What a tick of the tree asks the agent to do, applied by the behavior system once evaluation is done.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Intent {
+    MoveTo(Vec3),
+    Speak,
+    Listen,
+    Idle,
+}
+
+/// Read only view of the agent and its world a tree is evaluated against.
+pub struct Blackboard<'a> {
+    pub id: &'a ID,
+    pub position: Vec3,
+    pub target: Option<Vec3>,
+    pub personality: &'a Personality,
+    pub opinions: &'a Opinions,
+    pub transform_state: &'a TransformState,
+    pub location_directory: &'a LocationDirectory,
+}
+
+impl Blackboard<'_> {
+    fn favorite_id(&self) -> Option<String> {
+        let favorite_id = self.opinions.get_fav_person_id();
+
+        if favorite_id == self.id.0 {
+            None
+        } else {
+            Some(favorite_id)
+        }
+    }
+
+    fn distance_to(&self, person_id: &String) -> Option<f32> {
+        self.transform_state
+            .get(person_id)
+            .map(|transform| self.position.distance(transform.translation))
+    }
+
+    fn resolve(&self, target: &Target) -> Option<Vec3> {
+        match target {
+            Target::Favorite => self
+                .favorite_id()
+                .and_then(|favorite_id| self.transform_state.get(&favorite_id))
+                .map(|transform| transform.translation),
+            Target::FavoriteLocation => self
+                .opinions
+                .get_fav_location_id()
+                .and_then(|location_id| self.resolve(&Target::Location(location_id))),
+            Target::Location(location_id) => {
+                let zone = self.location_directory.get(location_id)?;
+
+                match self.target {
+                    Some(current) if zone.contains(current) => Some(current),
+                    _ => Some(zone.random_point()),
+                }
+            }
+            Target::Random => match self.target {
+                Some(current) if current.distance(self.position) > ARRIVAL_RADIUS / 4.0 => {
+                    Some(current)
+                }
+                _ => {
+                    let angle: f32 = thread_rng().gen_range(0.0..std::f32::consts::TAU);
+                    Some(self.position + Vec3::new(angle.cos(), angle.sin(), 0.0) * WANDER_DISTANCE)
+                }
+            },
+            Target::Point(x, y) => Some(Vec3::new(*x, *y, self.position.z)),
+        }
+    }
+}
+
+impl Condition {
+    fn check(&self, blackboard: &Blackboard) -> bool {
+        match self {
+            Condition::Chance(probability) => {
+                thread_rng().gen_bool(probability.clamp(0.0, 1.0) as f64)
+            }
+            Condition::TraitAbove(personality_trait, value) => {
+                personality_trait.value(blackboard.personality) > *value
+            }
+            Condition::TraitBelow(personality_trait, value) => {
+                personality_trait.value(blackboard.personality) < *value
+            }
+            Condition::FavoriteAbove(value) => blackboard
+                .favorite_id()
+                .and_then(|favorite_id| blackboard.opinions.people.get(&favorite_id))
+                .is_some_and(|opinion| opinion.likeability > *value),
+            Condition::RivalWithin(distance) => nearest_rival(
+                blackboard.id,
+                blackboard.position,
+                blackboard.opinions,
+                blackboard.transform_state,
+            )
+            .is_some_and(|(_, rival_distance)| rival_distance < *distance),
+            Condition::FavoriteWithin(distance) => blackboard
+                .favorite_id()
+                .and_then(|favorite_id| blackboard.distance_to(&favorite_id))
+                .is_some_and(|favorite_distance| favorite_distance < *distance),
+            Condition::KnowsAtLeast(count) => blackboard.opinions.people.len() >= *count,
+            Condition::InLocation(location_id) => blackboard
+                .location_directory
+                .get(location_id)
+                .is_some_and(|zone| zone.contains(blackboard.position)),
+        }
+    }
+}
+
+impl Action {
+    fn perform(&self, blackboard: &Blackboard, intents: &mut Vec<Intent>) -> bool {
+        let intent = match self {
+            Action::MoveTo(target) => match blackboard.resolve(target) {
+                Some(point) => Intent::MoveTo(point),
+                None => return false,
+            },
+            Action::Flee => {
+                let rival = nearest_rival(
+                    blackboard.id,
+                    blackboard.position,
+                    blackboard.opinions,
+                    blackboard.transform_state,
+                );
+
+                match rival.and_then(|(rival_id, _)| blackboard.transform_state.get(&rival_id)) {
+                    Some(rival_transform) => {
+                        let away =
+                            (blackboard.position - rival_transform.translation).normalize_or_zero();
+                        Intent::MoveTo(blackboard.position + away * WANDER_DISTANCE)
+                    }
+                    None => return false,
+                }
+            }
+            Action::Speak => Intent::Speak,
+            Action::Listen => Intent::Listen,
+            Action::Idle => Intent::Idle,
+        };
+
+        intents.push(intent);
+        true
+    }
+}
+
+impl Node {
+    /// A failing node takes back the intents its children added, so only the branches that won get acted upon.
+    pub fn tick(&self, blackboard: &Blackboard, intents: &mut Vec<Intent>) -> bool {
+        let mark = intents.len();
+        let succeeded = match self {
+            Node::Sequence(children) => {
+                children.iter().all(|child| child.tick(blackboard, intents))
+            }
+            Node::Selector(children) => {
+                children.iter().any(|child| child.tick(blackboard, intents))
+            }
+            Node::Inverter(child) => !child.tick(blackboard, intents),
+            Node::Condition(condition) => condition.check(blackboard),
+            Node::Action(action) => action.perform(blackboard, intents),
+        };
+
+        if !succeeded {
+            intents.truncate(mark);
+        }
+        succeeded
+    }
+}
+
+#[derive(Component)]
+pub struct BehaviorTree {
+    pub root: Node,
+    pub listening: bool,
+}
+
+impl BehaviorTree {
+    pub fn load(path: &str) -> Result<Node, String> {
+        let source = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+
+        ron::from_str(&source).map_err(|e| format!("{}: {}", path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::PersonalOpinion;
+
+    fn personality(extraversion: f32) -> Personality {
+        Personality {
+            openness: 0.5,
+            conscientiousness: 0.5,
+            extraversion,
+            agreeableness: 0.5,
+            neuroticism: 0.5,
+        }
+    }
+
+    fn node(source: &str) -> Node {
+        ron::from_str(source).unwrap()
+    }
+
+    /// Ticks `root` for an agent at the origin, returns the outcome and the intents produced.
+    fn tick(
+        root: &Node,
+        personality: &Personality,
+        opinions: &Opinions,
+        transform_state: &TransformState,
+    ) -> (bool, Vec<Intent>) {
+        let id = ID("agent".to_string());
+        let location_directory = LocationDirectory::default();
+        let blackboard = Blackboard {
+            id: &id,
+            position: Vec3::ZERO,
+            target: None,
+            personality,
+            opinions,
+            transform_state,
+            location_directory: &location_directory,
+        };
+        let mut intents = Vec::new();
+        let succeeded = root.tick(&blackboard, &mut intents);
+
+        (succeeded, intents)
+    }
+
+    #[test]
+    fn gossip_tree_parses() {
+        let root = BehaviorTree::load("assets/behaviors/gossip.ron").unwrap();
+
+        assert!(matches!(root, Node::Selector(children) if children.len() == 6));
+    }
+
+    #[test]
+    fn sequence_stops_at_the_first_failure() {
+        let root = node("Sequence([Condition(TraitAbove(Extraversion, 50.0)), Action(Speak)])");
+        let opinions = Opinions::new("agent".to_string(), &personality(0.5));
+        let transform_state = TransformState::default();
+
+        assert_eq!(
+            tick(&root, &personality(0.2), &opinions, &transform_state),
+            (false, vec![])
+        );
+        assert_eq!(
+            tick(&root, &personality(0.8), &opinions, &transform_state),
+            (true, vec![Intent::Speak])
+        );
+    }
+
+    #[test]
+    fn selector_settles_for_the_first_success() {
+        let root = node(
+            "Selector([
+                Sequence([Condition(KnowsAtLeast(2)), Action(Listen)]),
+                Inverter(Condition(TraitBelow(Neuroticism, 10.0))),
+                Action(Idle),
+            ])",
+        );
+        let opinions = Opinions::new("agent".to_string(), &personality(0.5));
+        let transform_state = TransformState::default();
+
+        assert_eq!(
+            tick(&root, &personality(0.5), &opinions, &transform_state),
+            (true, vec![])
+        );
+    }
+
+    #[test]
+    fn rejected_branches_leave_no_intents_behind() {
+        let root = node(
+            "Selector([
+                Sequence([Action(Speak), Condition(TraitAbove(Extraversion, 90.0))]),
+                Action(Idle),
+            ])",
+        );
+        let opinions = Opinions::new("agent".to_string(), &personality(0.5));
+        let transform_state = TransformState::default();
+
+        assert_eq!(
+            tick(&root, &personality(0.5), &opinions, &transform_state),
+            (true, vec![Intent::Idle])
+        );
+    }
+
+    #[test]
+    fn gossip_tree_walks_towards_a_distant_favorite() {
+        let root = BehaviorTree::load("assets/behaviors/gossip.ron").unwrap();
+        let mut opinions = Opinions::new("agent".to_string(), &personality(0.8));
+        opinions.insert_opinion("friend".to_string(), PersonalOpinion::new(50.0, 1000.0));
+        let mut transform_state = TransformState::default();
+        let friend_position = Vec3::new(300.0, 0.0, 0.0);
+        transform_state.transforms.insert(
+            "friend".to_string(),
+            Transform::from_translation(friend_position),
+        );
+
+        assert_eq!(
+            tick(&root, &personality(0.8), &opinions, &transform_state),
+            (true, vec![Intent::MoveTo(friend_position)])
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! Relationship graph agents start the simulation with, either loaded from a file
+//! or generated from one of the standard random graph models.
+//!
+//! Agents are referred to by their index in spawn order, every tie is mutual.
+
+use crate::cli::{Args, NetworkKind};
+use rand::prelude::*;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+
+/// Rivals each agent gets in the `Rivals` model.
+pub const RIVALS_PER_AGENT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Tie {
+    Friend,
+    Rival,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Network {
+    pub ties: Vec<(usize, usize, Tie)>,
+}
+
+/// Parameters of the generated network, taken from the command line.
+pub struct NetworkConfig {
+    pub kind: NetworkKind,
+    /// Average number of ties per agent.
+    pub degree: usize,
+    /// Share of ties that are friendships, the rest are rivalries. Ignored by the stochastic block model.
+    pub friend_ratio: f64,
+    /// Chance to rewire each edge of the Watts-Strogatz ring lattice.
+    pub rewire_probability: f64,
+    pub factions: usize,
+    /// How many times likelier a tie is inside a faction than across factions.
+    pub faction_affinity: f64,
+}
+
+impl From<&Args> for NetworkConfig {
+    fn from(args: &Args) -> Self {
+        NetworkConfig {
+            kind: args.network,
+            degree: args.network_degree,
+            friend_ratio: args.friend_ratio,
+            rewire_probability: args.rewire_probability,
+            factions: args.sbm_blocks,
+            faction_affinity: args.sbm_affinity,
+        }
+    }
+}
+
+/// Faction an agent belongs to in the stochastic block model, agents are dealt out round robin.
+pub fn faction_of(index: usize, factions: usize) -> usize {
+    index % factions.max(1)
+}
+
+impl Network {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let source = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+
+        ron::from_str(&source).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn generate(config: &NetworkConfig, population: usize, rng: &mut impl Rng) -> Self {
+        let density = if population > 1 {
+            (config.degree as f64 / (population - 1) as f64).min(1.0)
+        } else {
+            0.0
+        };
+
+        let edges = match config.kind {
+            NetworkKind::Rivals => {
+                let ties = random_rivals(population, rng)
+                    .into_iter()
+                    .map(|(a, b)| (a, b, Tie::Rival))
+                    .collect();
+                return Network { ties };
+            }
+            NetworkKind::ErdosRenyi => erdos_renyi(population, density, rng),
+            NetworkKind::WattsStrogatz => {
+                watts_strogatz(population, config.degree, config.rewire_probability, rng)
+            }
+            NetworkKind::BarabasiAlbert => {
+                barabasi_albert(population, (config.degree / 2).max(1), rng)
+            }
+            NetworkKind::StochasticBlock => {
+                let ties = stochastic_block(population, config, rng)
+                    .into_iter()
+                    .map(|(a, b)| {
+                        if faction_of(a, config.factions) == faction_of(b, config.factions) {
+                            (a, b, Tie::Friend)
+                        } else {
+                            (a, b, Tie::Rival)
+                        }
+                    })
+                    .collect();
+                return Network { ties };
+            }
+        };
+
+        let ties = edges
+            .into_iter()
+            .map(|(a, b)| {
+                if rng.gen_bool(config.friend_ratio.clamp(0.0, 1.0)) {
+                    (a, b, Tie::Friend)
+                } else {
+                    (a, b, Tie::Rival)
+                }
+            })
+            .collect();
+
+        Network { ties }
+    }
+}
+
+/// Normalized so an undirected edge is only stored once.
+fn edge(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+fn sorted(edges: HashSet<(usize, usize)>) -> Vec<(usize, usize)> {
+    let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+    edges.sort_unstable();
+    edges
+}
+
+/// The original setup, every agent picks a couple of random rivals.
+fn random_rivals(n: usize, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+    let mut edges = HashSet::new();
+    if n < 2 {
+        return Vec::new();
+    }
+
+    for a in 0..n {
+        for _ in 0..RIVALS_PER_AGENT {
+            let b = (a + rng.gen_range(1..n)) % n;
+            edges.insert(edge(a, b));
+        }
+    }
+
+    sorted(edges)
+}
+
+/// Every pair is tied independently with probability `p`.
+fn erdos_renyi(n: usize, p: f64, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+    let mut edges = HashSet::new();
+    for a in 0..n {
+        for b in a + 1..n {
+            if rng.gen_bool(p.clamp(0.0, 1.0)) {
+                edges.insert((a, b));
+            }
+        }
+    }
+
+    sorted(edges)
+}
+
+/// Ring lattice of `k` nearest neighbors, each edge's far end rewired to a random agent with probability `beta`.
+fn watts_strogatz(n: usize, k: usize, beta: f64, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+    let mut edges = HashSet::new();
+    if n < 2 {
+        return Vec::new();
+    }
+    let half = (k / 2).clamp(1, (n - 1) / 2 + 1);
+
+    for a in 0..n {
+        for offset in 1..=half {
+            let b = (a + offset) % n;
+            if a != b {
+                edges.insert(edge(a, b));
+            }
+        }
+    }
+
+    let lattice = sorted(edges.clone());
+    for (a, b) in lattice {
+        if !rng.gen_bool(beta.clamp(0.0, 1.0)) {
+            continue;
+        }
+
+        let rewired = (0..n)
+            .filter(|c| *c != a && !edges.contains(&edge(a, *c)))
+            .choose(rng);
+        if let Some(c) = rewired {
+            edges.remove(&(a, b));
+            edges.insert(edge(a, c));
+        }
+    }
+
+    sorted(edges)
+}
+
+/// Preferential attachment, every newcomer ties to `m` agents picked in proportion to how many ties they already have.
+fn barabasi_albert(n: usize, m: usize, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+    let mut edges = HashSet::new();
+    let seed = (m + 1).min(n);
+    // every agent appears once per tie it has, so uniform draws favor the well connected
+    let mut endpoints: Vec<usize> = Vec::new();
+
+    for a in 0..seed {
+        for b in a + 1..seed {
+            edges.insert((a, b));
+            endpoints.extend([a, b]);
+        }
+    }
+
+    for newcomer in seed..n {
+        let mut targets = HashSet::new();
+        while targets.len() < m.min(newcomer) {
+            if let Some(target) = endpoints.choose(rng) {
+                targets.insert(*target);
+            } else {
+                targets.insert(rng.gen_range(0..newcomer));
+            }
+        }
+
+        for target in targets {
+            edges.insert(edge(newcomer, target));
+            endpoints.extend([newcomer, target]);
+        }
+    }
+
+    sorted(edges)
+}
+
+/// Ties inside a faction are `faction_affinity` times likelier than ties across, keeping the average degree.
+fn stochastic_block(n: usize, config: &NetworkConfig, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+    let factions = config.factions.max(1) as f64;
+    let faction_size = n as f64 / factions;
+    let affinity = config.faction_affinity.max(0.0);
+    let pairs_weight = affinity * (faction_size - 1.0).max(0.0) + (n as f64 - faction_size);
+
+    let p_out = if pairs_weight > 0.0 {
+        config.degree as f64 / pairs_weight
+    } else {
+        0.0
+    };
+    let p_in = affinity * p_out;
+
+    let mut edges = HashSet::new();
+    for a in 0..n {
+        for b in a + 1..n {
+            let p = if faction_of(a, config.factions) == faction_of(b, config.factions) {
+                p_in
+            } else {
+                p_out
+            };
+            if rng.gen_bool(p.clamp(0.0, 1.0)) {
+                edges.insert((a, b));
+            }
+        }
+    }
+
+    sorted(edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(7)
+    }
+
+    fn config(kind: NetworkKind) -> NetworkConfig {
+        NetworkConfig {
+            kind,
+            degree: 4,
+            friend_ratio: 0.5,
+            rewire_probability: 0.1,
+            factions: 2,
+            faction_affinity: 8.0,
+        }
+    }
+
+    fn degrees(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+        let mut degrees = vec![0; n];
+        for (a, b) in edges {
+            degrees[*a] += 1;
+            degrees[*b] += 1;
+        }
+        degrees
+    }
+
+    #[test]
+    fn generated_ties_are_valid() {
+        for kind in [
+            NetworkKind::Rivals,
+            NetworkKind::ErdosRenyi,
+            NetworkKind::WattsStrogatz,
+            NetworkKind::BarabasiAlbert,
+            NetworkKind::StochasticBlock,
+        ] {
+            let network = Network::generate(&config(kind), 60, &mut rng());
+            let mut seen = HashSet::new();
+
+            assert!(!network.ties.is_empty(), "{:?}", kind);
+            for (a, b, _) in network.ties.iter() {
+                assert!(a < b && *b < 60, "{:?}", kind);
+                assert!(seen.insert((*a, *b)), "{:?}", kind);
+            }
+        }
+    }
+
+    #[test]
+    fn watts_strogatz_without_rewiring_is_a_ring_lattice() {
+        let edges = watts_strogatz(10, 4, 0.0, &mut rng());
+
+        assert_eq!(edges.len(), 20);
+        assert!(degrees(10, &edges).iter().all(|degree| *degree == 4));
+    }
+
+    #[test]
+    fn barabasi_albert_grows_hubs() {
+        let edges = barabasi_albert(200, 2, &mut rng());
+        let degrees = degrees(200, &edges);
+
+        assert_eq!(edges.len(), 3 + 2 * 197);
+        assert!(*degrees.iter().max().unwrap() > 4 * 2 * 2);
+    }
+
+    #[test]
+    fn block_model_ties_friends_within_and_rivals_across_factions() {
+        let network = Network::generate(&config(NetworkKind::StochasticBlock), 80, &mut rng());
+        let friends = network
+            .ties
+            .iter()
+            .filter(|(_, _, tie)| *tie == Tie::Friend)
+            .count();
+
+        assert!(friends > 2 * (network.ties.len() - friends));
+        for (a, b, tie) in network.ties.iter() {
+            assert_eq!(*tie == Tie::Friend, faction_of(*a, 2) == faction_of(*b, 2));
+        }
+    }
+}
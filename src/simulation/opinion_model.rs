@@ -0,0 +1,251 @@
+//! Interchangeable rules for how a heard claim moves the listener's opinion of its subject,
+//! so classic opinion dynamics models can be compared on the same simulation.
+//!
+//! Trust learning from contradictions happens before any model runs and is shared by all of them.
+
+use super::{PersonalOpinion, HEARSAY_WEIGHT};
+use crate::cli::OpinionModelKind;
+
+pub const DEGROOT_WEIGHT: f64 = 0.3;
+pub const CONVERGENCE_RATE: f64 = 0.3;
+pub const MAX_CERTAINTY: f64 = 50.0;
+/// Credibility of the agent's own experience, twice that of a fully trusted speaker.
+pub const FIRSTHAND_CREDIBILITY: f64 = 2.0;
+pub const FIRSTHAND_PRECISION: f64 = 10.0;
+
+/// A claim about a subject's likeability, as heard by the listener.
+pub struct Claim {
+    /// Likeability of the subject according to the speaker, -100..100.
+    pub value: f64,
+    /// Listener's trust in the speaker, -100..100.
+    pub speaker_trust: f64,
+    /// Listener's personality driven multiplier on opinion shifts.
+    pub susceptibility: f64,
+    /// The listener experienced it directly rather than hearing it from someone.
+    pub firsthand: bool,
+}
+
+impl Claim {
+    /// Trust in the speaker mapped into -1..1, or FIRSTHAND_CREDIBILITY for the listener's own experience.
+    fn credibility(&self) -> f64 {
+        if self.firsthand {
+            FIRSTHAND_CREDIBILITY
+        } else {
+            self.speaker_trust / 100.0
+        }
+    }
+
+    /// Credibility with distrust counting as no weight at all.
+    fn weight(&self) -> f64 {
+        self.credibility().max(0.0)
+    }
+}
+
+pub trait OpinionModel: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Updates the listener's opinion of the subject in place.
+    fn update(&self, held: &mut PersonalOpinion, claim: &Claim);
+}
+
+impl From<OpinionModelKind> for Box<dyn OpinionModel> {
+    fn from(kind: OpinionModelKind) -> Self {
+        match kind {
+            OpinionModelKind::Accumulate => Box::new(TrustWeightedAccumulation),
+            OpinionModelKind::Degroot => Box::new(DeGroot),
+            OpinionModelKind::BoundedConfidence => Box::new(BoundedConfidence { bound: 40.0 }),
+            OpinionModelKind::Bayesian => Box::new(Bayesian),
+        }
+    }
+}
+
+/// The simulation's own rule, the claim accumulates into the seed scaled by its credibility.
+/// A distrusted speaker makes the listener lean the other way.
+pub struct TrustWeightedAccumulation;
+
+impl OpinionModel for TrustWeightedAccumulation {
+    fn name(&self) -> &'static str {
+        "accumulate"
+    }
+
+    fn update(&self, held: &mut PersonalOpinion, claim: &Claim) {
+        held.adjust_likeability(
+            HEARSAY_WEIGHT * claim.susceptibility * claim.credibility() * claim.value,
+        );
+    }
+}
+
+/// DeGroot averaging, the held opinion becomes a weighted average of itself and the claim.
+pub struct DeGroot;
+
+impl OpinionModel for DeGroot {
+    fn name(&self) -> &'static str {
+        "degroot"
+    }
+
+    fn update(&self, held: &mut PersonalOpinion, claim: &Claim) {
+        let weight = (DEGROOT_WEIGHT * claim.susceptibility * claim.weight()).min(1.0);
+        held.set_likeability((1.0 - weight) * held.likeability + weight * claim.value);
+    }
+}
+
+/// Deffuant bounded confidence, claims further than `bound` from the held opinion are ignored,
+/// closer ones pull the opinion part of the way. Firsthand experience is never ignored.
+/// Interactions are pairwise, so this stands in for Hegselmann-Krause which needs synchronous neighborhoods.
+pub struct BoundedConfidence {
+    pub bound: f64,
+}
+
+impl OpinionModel for BoundedConfidence {
+    fn name(&self) -> &'static str {
+        "bounded-confidence"
+    }
+
+    fn update(&self, held: &mut PersonalOpinion, claim: &Claim) {
+        let difference = claim.value - held.likeability;
+        if difference.abs() >= self.bound && !claim.firsthand {
+            return;
+        }
+
+        let firsthand_boost = if claim.firsthand {
+            FIRSTHAND_CREDIBILITY
+        } else {
+            1.0
+        };
+        let rate = (CONVERGENCE_RATE * claim.susceptibility * firsthand_boost).min(1.0);
+        held.set_likeability(held.likeability + rate * difference);
+    }
+}
+
+/// Gaussian belief update, the held opinion is a mean with a precision that grows with every claim heard,
+/// claims count with a precision given by trust in the speaker.
+pub struct Bayesian;
+
+impl OpinionModel for Bayesian {
+    fn name(&self) -> &'static str {
+        "bayesian"
+    }
+
+    fn update(&self, held: &mut PersonalOpinion, claim: &Claim) {
+        let claim_precision = if claim.firsthand {
+            claim.susceptibility * FIRSTHAND_PRECISION
+        } else {
+            let trust = claim.weight().min(0.99);
+            claim.susceptibility * trust / (1.0 - trust)
+        };
+        if claim_precision <= 0.0 {
+            return;
+        }
+
+        let prior_precision = held.certainty;
+        let posterior = (prior_precision * held.likeability + claim_precision * claim.value)
+            / (prior_precision + claim_precision);
+
+        held.certainty = (prior_precision + claim_precision).min(MAX_CERTAINTY);
+        held.set_likeability(posterior);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(value: f64, speaker_trust: f64) -> Claim {
+        Claim {
+            value,
+            speaker_trust,
+            susceptibility: 1.0,
+            firsthand: false,
+        }
+    }
+
+    fn experience(value: f64) -> Claim {
+        Claim {
+            value,
+            speaker_trust: 0.0,
+            susceptibility: 1.0,
+            firsthand: true,
+        }
+    }
+
+    fn opinion(likeability: f64) -> PersonalOpinion {
+        let mut opinion = PersonalOpinion::new(0.0, 0.0);
+        opinion.set_likeability(likeability);
+        opinion
+    }
+
+    #[test]
+    fn degroot_moves_towards_the_claim_by_trust() {
+        let mut trusted = opinion(0.0);
+        DeGroot.update(&mut trusted, &claim(50.0, 100.0));
+        assert!((trusted.likeability - 15.0).abs() < 1e-6);
+
+        let mut distrusted = opinion(0.0);
+        DeGroot.update(&mut distrusted, &claim(50.0, -100.0));
+        assert!(distrusted.likeability.abs() < 1e-6);
+    }
+
+    #[test]
+    fn bounded_confidence_ignores_distant_claims() {
+        let model = BoundedConfidence { bound: 40.0 };
+
+        let mut held = opinion(-30.0);
+        model.update(&mut held, &claim(60.0, 100.0));
+        assert!((held.likeability + 30.0).abs() < 1e-6);
+
+        model.update(&mut held, &claim(0.0, 100.0));
+        assert!((held.likeability + 21.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bayesian_grows_more_certain_and_harder_to_move() {
+        let mut held = opinion(0.0);
+
+        Bayesian.update(&mut held, &claim(60.0, 50.0));
+        let first_shift = held.likeability;
+        Bayesian.update(&mut held, &claim(60.0 + first_shift, 50.0));
+        let second_shift = held.likeability - first_shift;
+
+        assert!(first_shift > 0.0);
+        assert!(second_shift < first_shift);
+        assert!(held.certainty > 2.0);
+    }
+
+    #[test]
+    fn firsthand_experience_moves_every_model_more_than_trusted_hearsay() {
+        let models: [Box<dyn OpinionModel>; 4] = [
+            Box::new(TrustWeightedAccumulation),
+            Box::new(DeGroot),
+            Box::new(BoundedConfidence { bound: 40.0 }),
+            Box::new(Bayesian),
+        ];
+
+        for model in models.iter() {
+            let mut heard = opinion(0.0);
+            model.update(&mut heard, &claim(30.0, 90.0));
+            let mut experienced = opinion(0.0);
+            model.update(&mut experienced, &experience(30.0));
+
+            assert!(
+                experienced.likeability > heard.likeability,
+                "{}",
+                model.name()
+            );
+        }
+
+        let mut held = opinion(-30.0);
+        BoundedConfidence { bound: 40.0 }.update(&mut held, &experience(60.0));
+        assert!(held.likeability > -30.0);
+    }
+
+    #[test]
+    fn accumulation_follows_the_speakers_credibility() {
+        let mut trusted = opinion(0.0);
+        TrustWeightedAccumulation.update(&mut trusted, &claim(50.0, 80.0));
+        assert!(trusted.likeability > 0.0);
+
+        let mut distrusted = opinion(0.0);
+        TrustWeightedAccumulation.update(&mut distrusted, &claim(50.0, -80.0));
+        assert!(distrusted.likeability < 0.0);
+    }
+}
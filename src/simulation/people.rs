@@ -0,0 +1,151 @@
+//! Opinions an agent holds of people, along with a ranking of them by likeability.
+//!
+//! Every change to an opinion goes through `People`, so the ranking can never fall out of step with the opinions.
+
+use super::PersonalOpinion;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Index;
+
+#[derive(Default)]
+pub struct People {
+    owner_id: String,
+    opinions: HashMap<String, PersonalOpinion>,
+    /// Everyone but the owner, from most to least liked.
+    ranking: Vec<(String, f64)>,
+}
+
+impl People {
+    pub fn new(owner_id: String) -> Self {
+        People {
+            owner_id,
+            opinions: HashMap::new(),
+            ranking: Vec::new(),
+        }
+    }
+
+    pub fn get<Q>(&self, person_id: &Q) -> Option<&PersonalOpinion>
+    where
+        String: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.opinions.get(person_id)
+    }
+
+    pub fn contains_key<Q>(&self, person_id: &Q) -> bool
+    where
+        String: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.opinions.contains_key(person_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.opinions.len()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.opinions.keys()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &PersonalOpinion)> {
+        self.opinions.iter()
+    }
+
+    /// Everyone but the owner, from most to least liked.
+    pub fn ranking(&self) -> &[(String, f64)] {
+        &self.ranking
+    }
+
+    pub fn insert(&mut self, person_id: String, opinion: PersonalOpinion) {
+        self.opinions.insert(person_id.clone(), opinion);
+        self.refresh_rank(&person_id);
+    }
+
+    /// Changes the opinion held of the person and moves them to their new place in the ranking,
+    /// None when the person is unknown.
+    pub fn update<Q, R>(
+        &mut self,
+        person_id: &Q,
+        change: impl FnOnce(&mut PersonalOpinion) -> R,
+    ) -> Option<R>
+    where
+        String: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let person_id = self.opinions.get_key_value(person_id)?.0.clone();
+        let result = change(self.opinions.get_mut::<str>(&person_id).unwrap());
+
+        self.refresh_rank(&person_id);
+        Some(result)
+    }
+
+    pub fn remove<Q>(&mut self, person_id: &Q) -> Option<PersonalOpinion>
+    where
+        String: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (person_id, opinion) = self.opinions.remove_entry(person_id)?;
+        self.ranking
+            .retain(|(ranked_id, _)| *ranked_id != person_id);
+
+        Some(opinion)
+    }
+
+    /// Changes every opinion but the one the owner holds of itself, then lets the ones whose likeability
+    /// overtook a neighbour's move to their new place.
+    ///
+    /// A change that touches everyone, like fading, mostly keeps the order, so the insertion pass only
+    /// shifts the few displaced people instead of sorting the whole ranking on every tick.
+    pub fn update_others(&mut self, mut change: impl FnMut(&mut PersonalOpinion)) {
+        for (person_id, opinion) in self.opinions.iter_mut() {
+            if *person_id != self.owner_id {
+                change(opinion);
+            }
+        }
+
+        for (person_id, likeability) in self.ranking.iter_mut() {
+            *likeability = self.opinions[person_id.as_str()].likeability;
+        }
+
+        for sorted in 1..self.ranking.len() {
+            let mut index = sorted;
+            while index > 0 && self.ranking[index - 1].1 < self.ranking[index].1 {
+                self.ranking.swap(index - 1, index);
+                index -= 1;
+            }
+        }
+    }
+
+    /// Moves the person to its place in the ranking after its likeability changed, a single removal and a binary search insert.
+    fn refresh_rank(&mut self, person_id: &String) {
+        if *person_id == self.owner_id {
+            return;
+        }
+
+        if let Some(index) = self.ranking.iter().position(|(id, _)| id == person_id) {
+            self.ranking.remove(index);
+        }
+
+        if let Some(opinion) = self.opinions.get(person_id) {
+            let likeability = opinion.likeability;
+            let index = self
+                .ranking
+                .partition_point(|(_, other)| *other > likeability);
+            self.ranking.insert(index, (person_id.clone(), likeability));
+        }
+    }
+}
+
+impl<Q> Index<&Q> for People
+where
+    String: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+{
+    type Output = PersonalOpinion;
+
+    fn index(&self, person_id: &Q) -> &PersonalOpinion {
+        &self.opinions[person_id]
+    }
+}